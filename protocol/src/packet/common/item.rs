@@ -179,7 +179,7 @@ impl<Readable: AsyncRead + Unpin> ReadCwData<Item> for Readable {
 			mainkind = recipe as _;
 		}
 		//SAFETY: this value gets validated below
-		let kind = unsafe { transmute([mainkind, subkind]) };
+		let kind = unsafe { transmute::<[u8; 2], Kind>([mainkind, subkind]) };
 
 		let item = Item {
 			kind,
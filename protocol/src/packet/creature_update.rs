@@ -15,6 +15,7 @@ use crate::packet::creature_update::equipment::Slot;
 use crate::packet::creature_update::multipliers::Multiplier;
 use crate::packet::creature_update::skill_tree::Skill;
 use crate::utils::ArrayWrapper;
+use crate::utils::compression::MeasureCompression;
 
 pub mod equipment;
 pub mod skill_tree;
@@ -251,6 +252,9 @@ impl<Writable: AsyncWrite + Unpin> WriteCwData<CreatureUpdate> for Writable {
 			if let Some(it) = &creature_update.mana_cubes        { encoder.write_arbitrary(it).await?; }
 
 			encoder.shutdown().await?;
+			if let Some(statistics) = Writable::compression_statistics(self) { //not `self.compression_statistics()`, as that would resolve to the impl for `&mut Writable`
+				statistics.record(encoder.total_in(), encoder.total_out());
+			}
 		};
 
 		self.write_i32_le(buffer.len() as _).await?;
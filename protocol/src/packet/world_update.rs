@@ -12,6 +12,7 @@ use crate::{ReadCwData, WriteCwData};
 use crate::packet::{Hit, Projectile, StatusEffect, WorldUpdate};
 use crate::packet::common::{CreatureId, Hitbox, Item, Race};
 use crate::packet::world_update::loot::GroundItem;
+use crate::utils::compression::MeasureCompression;
 
 use self::mission::*;
 use self::p48::*;
@@ -76,6 +77,9 @@ impl<Writable: AsyncWrite + Unpin> WriteCwData<WorldUpdate> for Writable {
 		encoder.write_cw_data(&world_update.missions      ).await?;
 
 		encoder.shutdown().await?;
+		if let Some(statistics) = Writable::compression_statistics(self) { //see [crate::packet::creature_update]
+			statistics.record(encoder.total_in(), encoder.total_out());
+		}
 
 		self.write_i32_le(buffer.len() as _).await?;
 		self.write_all(&buffer).await
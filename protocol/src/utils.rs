@@ -14,6 +14,7 @@ use crate::utils::constants::SIZE_BLOCK;
 use crate::utils::io_extensions::{ReadArbitrary, WriteArbitrary};
 
 pub mod io_extensions;
+pub mod compression;
 pub mod flagset;
 pub mod constants;
 
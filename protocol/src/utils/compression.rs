@@ -0,0 +1,67 @@
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+use std::task::{Context, Poll};
+
+use tokio::io::{self, AsyncWrite};
+
+///running totals over the compressed packets written to a writer that measures them, see [MeasureCompression]
+#[derive(Debug, Default)]
+pub struct CompressionStatistics {
+	pub uncompressed_bytes: AtomicU64,
+	pub compressed_bytes: AtomicU64
+}
+
+impl CompressionStatistics {
+	pub(crate) fn record(&self, uncompressed: u64, compressed: u64) {
+		self.uncompressed_bytes.fetch_add(uncompressed, Relaxed);
+		self.compressed_bytes.fetch_add(compressed, Relaxed);
+	}
+}
+
+///lets a writer collect statistics about the compressed packets written to it. writers don't by default
+pub trait MeasureCompression {
+	fn compression_statistics(&self) -> Option<&CompressionStatistics>;
+}
+
+impl<Writable> MeasureCompression for Writable {
+	default fn compression_statistics(&self) -> Option<&CompressionStatistics> {
+		None
+	}
+}
+
+///an in-memory buffer which measures the compressed packets written to it
+#[derive(Debug)]
+pub struct MeasuredBuffer<'statistics> {
+	pub data: Vec<u8>,
+	statistics: &'statistics CompressionStatistics
+}
+
+impl<'statistics> MeasuredBuffer<'statistics> {
+	pub const fn new(statistics: &'statistics CompressionStatistics) -> Self {
+		Self {
+			data: vec![],
+			statistics
+		}
+	}
+}
+
+impl MeasureCompression for MeasuredBuffer<'_> {
+	fn compression_statistics(&self) -> Option<&CompressionStatistics> {
+		Some(self.statistics)
+	}
+}
+
+impl AsyncWrite for MeasuredBuffer<'_> {
+	fn poll_write(mut self: Pin<&mut Self>, context: &mut Context<'_>, data: &[u8]) -> Poll<io::Result<usize>> {
+		Pin::new(&mut self.data).poll_write(context, data)
+	}
+
+	fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.data).poll_flush(context)
+	}
+
+	fn poll_shutdown(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.data).poll_shutdown(context)
+	}
+}
@@ -1,6 +1,6 @@
+use std::sync::Arc;
 use std::time::Duration;
 
-use config::{Config, ConfigError};
 use futures::future::join_all;
 use tap::Pipe;
 use tokio::time::sleep;
@@ -10,13 +10,25 @@ use protocol::packet::world_update::{Sound, sound};
 use protocol::utils::sound_position_of;
 use protocol::packet::world_update::sound::Kind::{MenuOpen2, MenuClose2};
 
+use crate::addon::afk::Afk;
+use crate::addon::audit_log::AuditLog;
 use crate::addon::balancing::Balancing;
+use crate::addon::bans::Bans;
+use crate::addon::chat_moderation::ChatModeration;
 use crate::addon::command_manager::CommandManager;
 use crate::addon::discord_integration::DiscordIntegration;
+use crate::addon::flood_protection::FloodProtection;
+use crate::addon::name_policy::NamePolicy;
+use crate::addon::permissions::Permissions;
+use crate::addon::profiles::Profiles;
+use crate::addon::reports::Reports;
+use crate::addon::rcon::Rcon;
+use crate::addon::voting::Voting;
+use crate::addon::whitelist::Whitelist;
+use crate::server::config::ServerConfig;
 use crate::server::creature::Creature;
 use crate::server::player::Player;
 use crate::server::Server;
-use crate::SERVER;
 
 use listforge_api::ListforgeApi;
 
@@ -25,11 +37,23 @@ use self::npcs::Npcs as Npcs;
 
 pub mod anti_cheat;
 pub mod traffic_filter;
+pub mod flood_protection;
 pub mod balancing;
 pub mod discord_integration;
 pub mod command_manager;
+pub mod permissions;
+pub mod bans;
+pub mod chat_moderation;
+pub mod profiles;
+pub mod name_policy;
+pub mod whitelist;
+pub mod audit_log;
+pub mod reports;
+pub mod voting;
+pub mod afk;
 pub mod pvp;
 pub mod listforge_api;
+pub mod rcon;
 pub mod kill_feed;
 pub mod models;
 pub mod npcs;
@@ -38,29 +62,66 @@ pub struct Addons {
 	pub discord_integration: DiscordIntegration,
 	pub balancing: Balancing,
 	pub command_manager: CommandManager,
+	pub permissions: Permissions,
+	pub bans: Bans,
+	pub chat_moderation: ChatModeration,
+	pub profiles: Profiles,
+	pub name_policy: NamePolicy,
+	pub whitelist: Whitelist,
+	pub audit_log: AuditLog,
+	pub reports: Reports,
+	pub voting: Voting,
+	pub afk: Afk,
+	pub flood_protection: FloodProtection,
 	pub listforge_api: ListforgeApi,
+	pub rcon: Rcon,
 	pub models: Models,
 	pub npcs: Npcs
 }
 
 impl Addons {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
-		let instance = Self {
-			discord_integration: DiscordIntegration::new(config)?,
-			balancing: Balancing::new(config)?,
-			command_manager: CommandManager::new(config)?,
-			listforge_api: ListforgeApi::new(config)?,
-			models: Models::new(config)?,
+	pub fn new(config: &ServerConfig) -> Self {
+		Self {
+			discord_integration: DiscordIntegration::new(config),
+			balancing: Balancing::new(config.balance.clone()),
+			command_manager: CommandManager::new(config),
+			permissions: Permissions::new(config),
+			bans: Bans::new(config),
+			chat_moderation: ChatModeration::new(config.chat.clone()),
+			profiles: Profiles::new(config),
+			name_policy: NamePolicy::new(config.names.clone()),
+			whitelist: Whitelist::new(config),
+			audit_log: AuditLog::new(config),
+			reports: Reports::new(config),
+			voting: Voting::new(config.voting.clone()),
+			afk: Afk::new(config.afk.clone()),
+			flood_protection: FloodProtection::new(config.packet_limits.clone()),
+			listforge_api: ListforgeApi::new(config),
+			rcon: Rcon::new(config),
+			models: Models::new(&config.models),
 			npcs: Npcs::new()
-		};
-
-		Ok(instance)
+		}
 	}
 	
-	pub async fn start(&self) {
-		self.listforge_api.run().await;
-		self.discord_integration.run();
-		freeze_time();
+	///swaps in everything that can change at runtime
+	pub async fn reload(&self, config: &ServerConfig) {
+		self.balancing.reload(config.balance.clone()).await;
+		self.command_manager.reload(config).await;
+		self.permissions.reload(config).await;
+		self.chat_moderation.reload(config.chat.clone()).await;
+		self.name_policy.reload(config.names.clone()).await;
+		self.whitelist.reload(config).await;
+		self.voting.reload(config.voting.clone()).await;
+		self.afk.reload(config.afk.clone()).await;
+		self.models.reload(&config.models).await;
+	}
+
+	pub async fn start(&self, server: &Arc<Server>) {
+		self.listforge_api.run(Arc::clone(server)).await;
+		self.rcon.run(Arc::clone(server)).await;
+		self.discord_integration.run(Arc::clone(server));
+		self.afk.run(Arc::clone(server));
+		freeze_time(Arc::clone(server));
 	}
 }
 
@@ -79,12 +140,12 @@ pub const fn fix_cutoff_animations(creature_update: &mut CreatureUpdate, previou
 	}
 }
 
-fn freeze_time() {
+fn freeze_time(server: Arc<Server>) {
 	tokio::spawn(async move {
 		let packet = IngameDatetime { time: 12 * 60 * 60 * 1000, day: 0 };
 
 		loop {
-			SERVER.broadcast(&packet, None).await;
+			server.broadcast(&packet, None).await;
 			sleep(Duration::from_secs(6)).await;
 		}
 	});
@@ -0,0 +1,148 @@
+use std::mem;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use config::ConfigError;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+use protocol::nalgebra::Vector3;
+use protocol::packet::CreatureUpdate;
+
+use crate::addon::permissions::{Issuer, AFK_EXEMPT};
+use crate::server::config::invalid;
+use crate::server::player::Player;
+use crate::server::utils::format_duration;
+use crate::server::Server;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AfkConfig {
+	pub mark_after: u64, //seconds, 0 disables
+	pub warn_after: u64, //seconds, 0 disables
+	pub kick_after: u64 //seconds, 0 disables
+}
+
+impl Default for AfkConfig {
+	fn default() -> Self {
+		Self {
+			mark_after: 300,
+			warn_after: 840,
+			kick_after: 900
+		}
+	}
+}
+
+impl AfkConfig {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.warn_after > 0 && self.kick_after == 0 {
+			return invalid("afk.warn_after requires afk.kick_after");
+		}
+		if self.warn_after >= self.kick_after && self.warn_after > 0 {
+			return invalid("afk.warn_after must be less than afk.kick_after");
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug)]
+pub struct PlayerData {
+	last_active: Instant,
+	marked: bool,
+	warned: bool
+}
+
+impl Default for PlayerData {
+	fn default() -> Self {
+		Self {
+			last_active: Instant::now(),
+			marked: false,
+			warned: false
+		}
+	}
+}
+
+///tracks when players last moved or chatted. idle ones get marked, warned and eventually kicked.
+///players permitted to [AFK_EXEMPT] only get marked
+pub struct Afk {
+	config: RwLock<AfkConfig>
+}
+
+impl Afk {
+	pub fn new(config: AfkConfig) -> Self {
+		Self {
+			config: RwLock::new(config)
+		}
+	}
+
+	pub async fn reload(&self, config: AfkConfig) {
+		*self.config.write().await = config;
+	}
+
+	pub fn run(&self, server: Arc<Server>) {
+		tokio::spawn(async move {
+			loop {
+				sleep(Duration::from_secs(1)).await;
+				check_everyone(&server).await;
+			}
+		});
+	}
+}
+
+pub async fn is_afk(player: &Player) -> bool {
+	player.addon_data.read().await.afk.marked
+}
+
+///walking around (as opposed to being pushed around) counts as activity
+pub async fn on_creature_update(source: &Player, packet: &CreatureUpdate) {
+	if packet.acceleration.is_some_and(|acceleration| acceleration != Vector3::zeros()) {
+		on_activity(source).await;
+	}
+}
+
+pub async fn on_activity(player: &Player) {
+	let mut addon_data = player.addon_data.write().await;
+	let afk = &mut addon_data.afk;
+	afk.last_active = Instant::now();
+	afk.warned = false;
+	let was_marked = mem::take(&mut afk.marked);
+	drop(addon_data);
+
+	if was_marked {
+		player.notify("you are no longer marked as afk").await;
+	}
+}
+
+async fn check_everyone(server: &Server) {
+	let config = server.addons.afk.config.read().await.clone();
+	let players = server.players.read().await.clone();
+
+	for player in players {
+		let exempt = server.addons.permissions.allows(Issuer::Player(&player), AFK_EXEMPT).await;
+
+		let mut addon_data = player.addon_data.write().await;
+		let afk = &mut addon_data.afk;
+		let idle = afk.last_active.elapsed();
+		let exceeds = |threshold: u64| threshold > 0 && idle >= Duration::from_secs(threshold);
+
+		let mark = exceeds(config.mark_after) && !afk.marked;
+		let warn = !exempt && exceeds(config.warn_after) && !afk.warned;
+		let kick = !exempt && exceeds(config.kick_after);
+		afk.marked |= mark;
+		afk.warned |= warn;
+		drop(addon_data);
+
+		if kick {
+			server.kick(&player, "afk").await;
+			continue;
+		}
+		if mark {
+			player.notify("you are now marked as afk").await;
+		}
+		if warn {
+			let remaining = Duration::from_secs(config.kick_after - config.warn_after); //see [AfkConfig::validate]
+			player.notify(format!("you will be kicked for being afk in {}, unless you move", format_duration(remaining))).await;
+		}
+	}
+}
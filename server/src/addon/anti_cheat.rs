@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::ops::RangeBounds;
 use std::result;
@@ -18,7 +19,26 @@ pub struct PlayerData {
 	combo_epoch: Option<Instant>,
 	last_lag_spike: Option<Instant>,
 	last_checked: Option<Instant>,
-	total_shift_nanos: i64
+	total_shift_nanos: i64,
+	near_misses: VecDeque<(Instant, String)>
+}
+
+///how many near misses are remembered per player
+const NEAR_MISSES: usize = 10;
+
+impl PlayerData {
+	///something suspicious that didn't get the player kicked, e.g. a violation by someone immune
+	pub fn note_near_miss(&mut self, description: String) {
+		if self.near_misses.len() == NEAR_MISSES {
+			self.near_misses.pop_front();
+		}
+		self.near_misses.push_back((Instant::now(), description));
+	}
+
+	///oldest first
+	pub fn near_misses(&self) -> impl Iterator<Item = &(Instant, String)> {
+		self.near_misses.iter()
+	}
 }
 
 #[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
@@ -45,13 +45,17 @@ pub(super) async fn check_for_timewarp(previous_state: &Creature, updated_state:
 
     if delta.abs() > Duration::from_millis(500).as_nanos() as _ {
         ac_data.last_lag_spike = Some(Instant::now());
+        ac_data.note_near_miss(format!("timewarp ignored as lag spike ({}ms off)", delta / 1_000_000));
         return Ok(());
     }
 
     ac_data.shift_epoch(delta as _);
 
-    (ac_data.total_shift_nanos.abs() / 1_000_000)
-        .ensure_at_most(2000, "timewarp.clockdesync")?;
+    let desync = ac_data.total_shift_nanos.abs() / 1_000_000;
+    if desync > 1000 {
+        ac_data.note_near_miss(format!("timewarp.clockdesync was {desync}, allowed is at most 2000"));
+    }
+    desync.ensure_at_most(2000, "timewarp.clockdesync")?;
 
 	Ok(())
 }
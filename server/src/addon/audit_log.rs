@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::fs::OpenOptions;
+use tokio::io::{self, AsyncWriteExt};
+use tokio::sync::RwLock;
+
+use crate::addon::command_manager::CommandResult;
+use crate::addon::permissions::Issuer;
+use crate::server::config::ServerConfig;
+use crate::server::utils::{log_error, unix_now};
+use crate::server::Server;
+
+const AUDIT_FILE: &str = "audit.jsonl";
+///how many of the latest entries are kept in memory for `/audit`, the file keeps everything
+const RECENT_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+	pub time: u64, //unix seconds
+	pub issuer: String,
+	pub action: String, //the command with its arguments
+	pub result: String
+}
+
+impl Display for Entry {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		write!(formatter, "{}: {} -> {}", self.issuer, self.action, self.result)
+	}
+}
+
+///every privileged or game-changing command, appended to a file in the data directory with 1 json object per line
+pub struct AuditLog {
+	path: PathBuf,
+	recent: RwLock<VecDeque<Entry>>
+}
+
+impl AuditLog {
+	pub fn new(config: &ServerConfig) -> Self {
+		let path = config.data_dir.join(AUDIT_FILE);
+		let mut recent = VecDeque::new();
+		match fs::read_to_string(&path) {
+			Ok(data) => {
+				//lines that can't be parsed are skipped rather than fatal, as nothing ever gets overwritten
+				for entry in data.lines().filter_map(|line| serde_json::from_str(line).ok()) {
+					if recent.len() == RECENT_ENTRIES {
+						recent.pop_front();
+					}
+					recent.push_back(entry);
+				}
+			}
+			Err(error) if error.kind() == NotFound => {}
+			Err(error) => panic!("failed to read audit log: {error}")
+		}
+
+		Self {
+			path,
+			recent: RwLock::new(recent)
+		}
+	}
+
+	///the latest `amount` entries containing `filter` (case insensitive), oldest first
+	pub async fn search(&self, filter: &str, amount: usize) -> Vec<Entry> {
+		let filter = filter.to_lowercase();
+
+		let mut entries = self.recent
+			.read()
+			.await
+			.iter()
+			.rev()
+			.filter(|entry| entry.to_string().to_lowercase().contains(&filter))
+			.take(amount)
+			.cloned()
+			.collect::<Vec<_>>();
+		entries.reverse();
+		entries
+	}
+
+	async fn append(&self, entry: Entry) -> io::Result<()> {
+		let mut recent = self.recent.write().await;//also keeps concurrent writes from interleaving
+
+		let mut line = serde_json::to_string(&entry)?;
+		line.push('\n');
+		OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.path)
+			.await?
+			.write_all(line.as_bytes())
+			.await?;
+
+		if recent.len() == RECENT_ENTRIES {
+			recent.pop_front();
+		}
+		recent.push_back(entry);
+		Ok(())
+	}
+}
+
+impl Server {
+	///records the action and forwards it to the discord admin channel
+	pub async fn audit(&self, issuer: String, action: String, result: String) {
+		let entry = Entry {
+			time: unix_now(),
+			issuer,
+			action,
+			result
+		};
+
+		self.addons.discord_integration.post(&format!("`{entry}`"), true).await;
+		_ = self.addons.audit_log
+			.append(entry)
+			.await
+			.inspect_err(|error| log_error("audit-log", error));
+	}
+}
+
+///includes the character name, as ids get reused
+pub async fn describe(issuer: Issuer<'_>) -> String {
+	match issuer {
+		Issuer::Player(player) => format!("{} ({issuer})", player.character.read().await.name),
+		_ => issuer.to_string()
+	}
+}
+
+///a short summary of what came out of a command
+pub fn outcome(result: &CommandResult) -> String {
+	match result {
+		Ok(None) => "ok".to_owned(),
+		Ok(Some(response)) => format!("ok ({})", response.lines().next().unwrap_or_default()),
+		Err(error) => format!("failed ({error})")
+	}
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::ptr;
 use std::time::{Duration, Instant};
 
-use config::{Config, ConfigError};
+use config::ConfigError;
 use serde::Deserialize;
 use tokio::sync::RwLock;
 
@@ -26,28 +26,33 @@ use protocol::packet::status_effect::Kind::{Anger, Swiftness};
 use protocol::packet::world_update::Sound;
 use protocol::packet::world_update::sound::Kind::{Magic01, SpikeTrap};
 
+use crate::server::config::invalid;
 use crate::server::creature::Creature;
 use crate::server::player::Player;
 use crate::server::Server;
 
 #[derive(Debug)]
 pub struct Balancing {
-	values: BalanceConfigValues,
+	values: RwLock<BalanceConfigValues>,
 	airtime_map: RwLock<HashMap<CreatureId, (Instant, bool)>>//todo: figure out a proper name
 }
 
 impl Balancing {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
-		Ok(Self {
-			values: config.get("balance")?,
+	pub fn new(values: BalanceConfigValues) -> Self {
+		Self {
+			values: RwLock::new(values),
 			airtime_map: Default::default()
-		})
+		}
+	}
+
+	pub async fn reload(&self, values: BalanceConfigValues) {
+		*self.values.write().await = values;
 	}
 
-	pub async fn on_hit(&self, server: &Server, hit: &mut Hit, source: &Creature, target: &Arc<Player>) {
+	pub async fn on_hit(&self, server: &Arc<Server>, hit: &mut Hit, source: &Creature, target: &Arc<Player>) {
 		let target_creature = target.character.read().await;
 		self.ignite(server, hit, source, Arc::clone(target)).await;
-		self.adjust_hit(hit, source, &target_creature);
+		self.adjust_hit(hit, source, &target_creature).await;
 	}
 
 	pub async fn track_airtime(&self, source: &Player) {
@@ -119,36 +124,36 @@ impl Balancing {
 		}
 	}
 
-	pub fn adjust_hit(&self, hit: &mut Hit, source: &Creature, target: &Creature) {
+	pub async fn adjust_hit(&self, hit: &mut Hit, source: &Creature, target: &Creature) {
+		let values = self.values.read().await;
+
 		let weapon_offense_multiplier = match source.equipment[RightWeapon].kind {
-			Weapon(weapon)  => *self.values.damage.get(&weapon.to_string().to_lowercase()).unwrap_or(&1.0),
+			Weapon(weapon)  => *values.damage.get(&weapon.to_string().to_lowercase()).unwrap_or(&1.0),
 			_               => 1.0
 		};
 
 		let weapon_stun_bonus = match source.equipment[RightWeapon].kind {
-			Weapon(weapon)  => *self.values.stun.get(&weapon.to_string().to_lowercase()).unwrap_or(&0),
+			Weapon(weapon)  => *values.stun.get(&weapon.to_string().to_lowercase()).unwrap_or(&0),
 			_               => 0
 		};
 
-		let class_offense_multiplier = *self
-			.values
+		let class_offense_multiplier = *values
 			.damage
 			.get(source.combat_class().config_name())
 			.unwrap_or(&1.0);
 
-		let class_stun_bonus = *self
-			.values
+		let class_stun_bonus = *values
 			.stun
 			.get(source.combat_class().config_name())
 			.unwrap_or(&0);
 
 		let effective_damage_multiplier =
-			self.values.damage["global"]
+			values.damage["global"]
 				* weapon_offense_multiplier
 				* class_offense_multiplier;
 
 		let effective_stun_bonus =
-			self.values.stun["global"]
+			values.stun["global"]
 				+ weapon_stun_bonus
 				+ class_stun_bonus;
 
@@ -162,12 +167,12 @@ impl Balancing {
 
 			let heal_multiplier =
 				if self_inflicted {
-					if source.combat_class() == WATER_MAGE { self.values.heal_self }
-					else                                   { *self.values.damage.get("unholy").unwrap_or(&1.0) }
+					if source.combat_class() == WATER_MAGE { values.heal_self }
+					else                                   { *values.damage.get("unholy").unwrap_or(&1.0) }
 					.sub(1.0) //self-heals are applied client side as well (bug), so we need to subtract the vanilla amount
 				}
 				else {
-					self.values.heal_other
+					values.heal_other
 				};
 			hit.damage *= heal_multiplier;
 		} else {//hurt
@@ -175,7 +180,7 @@ impl Balancing {
 			target.equipment
 				.iter()
 				.map(|item| match item.kind {
-					Weapon(Shield) => self.values.shield_defense,
+					Weapon(Shield) => values.shield_defense,
 					_              => 0.0
 				})
 				.sum::<f32>();
@@ -185,8 +190,8 @@ impl Balancing {
 	}
 
 	#[expect(clippy::cast_sign_loss, reason = "checked")]
-	async fn ignite(&self, server: &Server, hit: &Hit, source: &Creature, target: Arc<Player>) {
-		if source.combo % self.values.ignite_combo != 0 {
+	async fn ignite(&self, server: &Arc<Server>, hit: &Hit, source: &Creature, target: Arc<Player>) {
+		if source.combo % self.values.read().await.ignite_combo != 0 {
 			return;
 		}
 
@@ -238,18 +243,19 @@ impl Balancing {
 
 		let damage = hit.damage
 			* fire_spirit_count as f32
-			* self.values.damage["fire_spirit"]
+			* self.values.read().await.damage["fire_spirit"]
 			/ 10.0;
 
 		server.apply_dot(source, target, 10, 200, damage, sound::Kind::FireHit, Some(particles)).await;
 	}
 
-	pub fn adjust_manashield(&self, packet: &mut StatusEffect) {
-		packet.duration = self.values.manashield_duration;
-		if let Some(absolute_value) = self.values.manashield_capacity_absolute {
+	pub async fn adjust_manashield(&self, packet: &mut StatusEffect) {
+		let values = self.values.read().await;
+		packet.duration = values.manashield_duration;
+		if let Some(absolute_value) = values.manashield_capacity_absolute {
 			packet.modifier = absolute_value;
 		} else {
-			packet.modifier *= self.values.manashield_capacity_relative;
+			packet.modifier *= values.manashield_capacity_relative;
 		}
 	}
 }
@@ -276,8 +282,9 @@ pub fn adjust_blocking(hit: &mut Hit, attacker_creature: &Creature) {
 	hit.damage *= if has_shield { 0.5 } else { 0.0 };
 }
 
-#[derive(Debug, Deserialize)]
-struct BalanceConfigValues {
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BalanceConfigValues {
 	heal_self: f32,
 	heal_other: f32,
 	shield_defense: f32,
@@ -288,3 +295,37 @@ struct BalanceConfigValues {
 	manashield_capacity_absolute: Option<f32>,
 	ignite_combo: i32
 }
+
+impl Default for BalanceConfigValues {
+	fn default() -> Self {
+		Self {
+			heal_self: 1.0,
+			heal_other: 1.0,
+			shield_defense: 0.0,
+			damage: HashMap::from([("global".into(), 1.0), ("fire_spirit".into(), 1.0)]),
+			stun: HashMap::from([("global".into(), 0)]),
+			manashield_duration: 30000,
+			manashield_capacity_relative: 1.0,
+			manashield_capacity_absolute: None,
+			ignite_combo: 10
+		}
+	}
+}
+
+impl BalanceConfigValues {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		//these get indexed without a fallback
+		for key in ["global", "fire_spirit"] {
+			if !self.damage.contains_key(key) {
+				return invalid(format!("balance.damage must contain '{key}'"));
+			}
+		}
+		if !self.stun.contains_key("global") {
+			return invalid("balance.stun must contain 'global'");
+		}
+		if self.ignite_combo < 1 {
+			return invalid("balance.ignite_combo must be at least 1");
+		}
+		Ok(())
+	}
+}
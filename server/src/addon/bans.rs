@@ -0,0 +1,214 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use tokio::io;
+use tokio::sync::RwLock;
+
+use crate::server::config::ServerConfig;
+use crate::server::utils::{format_duration, unix_now, write_atomically};
+use crate::server::Server;
+
+const BANS_FILE: &str = "bans.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+	Name(String), //lowercase
+	Address(IpNet) //a single address is a range with the maximum prefix length
+}
+
+impl Target {
+	pub fn name(name: &str) -> Self {
+		Self::Name(name.to_lowercase())
+	}
+
+	///accepts single addresses as well as ranges in CIDR notation
+	pub fn parse_address(text: &str) -> Option<Self> {
+		text.parse::<IpNet>().ok()
+			.or_else(|| text.parse::<IpAddr>().ok().map(IpNet::from))
+			.map(Self::Address)
+	}
+
+	pub fn matches(&self, name: &str, address: IpAddr) -> bool {
+		match self {
+			Self::Name(banned) => *banned == name.to_lowercase(),
+			Self::Address(range) => range.contains(&address)
+		}
+	}
+}
+
+impl Display for Target {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Name(name) => write!(formatter, "{name}"),
+			Self::Address(range) if range.prefix_len() == range.max_prefix_len() => write!(formatter, "{}", range.addr()),
+			Self::Address(range) => write!(formatter, "{range}")
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ban {
+	pub target: Target,
+	pub reason: String, //may be empty
+	pub issuer: String,
+	pub created: u64, //unix seconds
+	pub expires: Option<u64> //unix seconds, none if permanent
+}
+
+impl Ban {
+	pub fn new(target: Target, reason: String, issuer: String, duration: Option<Duration>) -> Self {
+		let created = unix_now();
+
+		Self {
+			target,
+			reason,
+			issuer,
+			created,
+			expires: duration.map(|duration| created.saturating_add(duration.as_secs()))
+		}
+	}
+
+	fn is_expired(&self, now: u64) -> bool {
+		self.expires.is_some_and(|expires| expires <= now)
+	}
+
+	///none if permanent
+	pub fn remaining(&self) -> Option<Duration> {
+		self.expires.map(|expires| Duration::from_secs(expires.saturating_sub(unix_now())))
+	}
+
+	///shown to the banned player when trying to join
+	pub fn message(&self) -> String {
+		let mut message = "you are banned".to_owned();
+		if !self.reason.is_empty() {
+			message.push_str(&format!(": {}", self.reason));
+		}
+		if let Some(remaining) = self.remaining() {
+			message.push_str(&format!(" ({} left)", format_duration(remaining)));
+		}
+		message
+	}
+}
+
+impl Display for Ban {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		write!(formatter, "{} by {}", self.target, self.issuer)?;
+		if !self.reason.is_empty() {
+			write!(formatter, " ({})", self.reason)?;
+		}
+		match self.remaining() {
+			Some(remaining) => write!(formatter, ", {} left", format_duration(remaining)),
+			None => write!(formatter, ", permanent")
+		}
+	}
+}
+
+///bans by character name, address or address range. persisted in the data directory
+pub struct Bans {
+	path: PathBuf,
+	bans: RwLock<Vec<Ban>>
+}
+
+impl Bans {
+	pub fn new(config: &ServerConfig) -> Self {
+		let path = config.data_dir.join(BANS_FILE);
+		let bans = match fs::read(&path) {
+			Ok(data) => serde_json::from_slice(&data).expect("failed to parse bans"), //starting without them would overwrite the file
+			Err(error) if error.kind() == NotFound => vec![],
+			Err(error) => panic!("failed to read bans: {error}")
+		};
+
+		Self {
+			path,
+			bans: RwLock::new(bans)
+		}
+	}
+
+	///the active ban affecting the given name or address, if any
+	pub async fn find(&self, name: &str, address: IpAddr) -> Option<Ban> {
+		let now = unix_now();
+
+		self.bans
+			.read()
+			.await
+			.iter()
+			.find(|ban| !ban.is_expired(now) && ban.target.matches(name, address))
+			.cloned()
+	}
+
+	///active bans, oldest first
+	pub async fn list(&self) -> Vec<Ban> {
+		let now = unix_now();
+
+		self.bans
+			.read()
+			.await
+			.iter()
+			.filter(|ban| !ban.is_expired(now))
+			.cloned()
+			.collect()
+	}
+
+	///replaces any existing ban of the same target
+	async fn add(&self, ban: Ban) -> io::Result<()> {
+		let mut bans = self.bans.write().await;
+		bans.retain(|existing| existing.target != ban.target);
+		bans.push(ban);
+		self.save(&mut bans).await
+	}
+
+	async fn remove(&self, target: &Target) -> io::Result<Option<Ban>> {
+		let mut bans = self.bans.write().await;
+		let Some(index) = bans.iter().position(|ban| ban.target == *target)
+			else { return Ok(None) };
+
+		let ban = bans.remove(index);
+		self.save(&mut bans).await?;
+		Ok(Some(ban))
+	}
+
+	///also gets rid of expired bans
+	async fn save(&self, bans: &mut Vec<Ban>) -> io::Result<()> {
+		let now = unix_now();
+		bans.retain(|ban| !ban.is_expired(now));
+
+		write_atomically(&self.path, serde_json::to_vec_pretty(bans)?).await
+	}
+}
+
+impl Server {
+	///stores the ban, tells the admins and kicks everyone affected by it
+	pub async fn ban(&self, ban: Ban) -> io::Result<()> {
+		self.addons.bans.add(ban.clone()).await?;
+		self.notify_admins(format!("banned {ban}")).await;
+
+		let kick_reason = if ban.reason.is_empty() { "banned".to_owned() } else { format!("banned ({})", ban.reason) };
+		let players = self.players.read().await.clone();
+		for player in players {
+			let name = player.character.read().await.name.clone();
+			if ban.target.matches(&name, player.address.ip()) {
+				self.kick(&player, kick_reason.clone()).await;
+			}
+		}
+
+		Ok(())
+	}
+
+	///returns the lifted ban, none if there was nothing to lift
+	pub async fn unban(&self, target: &Target, issuer: &str) -> io::Result<Option<Ban>> {
+		let lifted = self.addons.bans.remove(target).await?;
+
+		if lifted.is_some() {
+			self.notify_admins(format!("{issuer} unbanned {target}")).await;
+		}
+
+		Ok(lifted)
+	}
+}
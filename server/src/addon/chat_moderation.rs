@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::iter::repeat_n;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use config::ConfigError;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::server::config::invalid;
+use crate::server::player::Player;
+use crate::server::utils::format_duration;
+use crate::server::Server;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WordAction {
+	///stars out the word
+	Replace,
+	///lets the message through, but tells the sender to behave
+	Warn,
+	///drops the whole message
+	Block
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChatConfig {
+	pub max_length: usize, //characters
+	pub repeat_window: u64, //seconds, 0 disables
+	pub caps_min_letters: usize, //0 disables
+	pub caps_ratio: f64,
+	pub block_links: bool,
+	pub allowed_links: Vec<String>,
+	pub words: HashMap<String, WordAction>
+}
+
+impl Default for ChatConfig {
+	fn default() -> Self {
+		Self {
+			max_length: 200,
+			repeat_window: 30,
+			caps_min_letters: 8,
+			caps_ratio: 0.7,
+			block_links: true,
+			allowed_links: vec![],
+			words: HashMap::new()
+		}
+	}
+}
+
+impl ChatConfig {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.max_length < 1 {
+			return invalid("chat.max_length must be at least 1");
+		}
+		if !(0.0..=1.0).contains(&self.caps_ratio) {
+			return invalid("chat.caps_ratio must be within 0.0..=1.0");
+		}
+		if self.allowed_links.iter().any(String::is_empty) {
+			return invalid("chat.allowed_links must not contain empty entries");
+		}
+		for word in self.words.keys() {
+			//messages get split into words at everything that isn't alphanumeric
+			if word.is_empty() || !word.chars().all(char::is_alphanumeric) || word.chars().any(char::is_uppercase) {
+				return invalid(format!("chat.words key '{word}' must be a single lowercase word"));
+			}
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Mute {
+	pub reason: String, //may be empty
+	expires: Option<Instant> //none if permanent
+}
+
+impl Mute {
+	///none if permanent
+	pub fn remaining(&self) -> Option<Duration> {
+		self.expires.map(|expires| expires.saturating_duration_since(Instant::now()))
+	}
+
+	fn is_expired(&self) -> bool {
+		self.expires.is_some_and(|expires| expires <= Instant::now())
+	}
+
+	///shown to the muted player whenever they try to chat
+	pub fn message(&self) -> String {
+		let mut message = "you are muted".to_owned();
+		if !self.reason.is_empty() {
+			message.push_str(&format!(": {}", self.reason));
+		}
+		if let Some(remaining) = self.remaining() {
+			message.push_str(&format!(" ({} left)", format_duration(remaining)));
+		}
+		message
+	}
+}
+
+///filters chat messages before they reach anyone. commands are exempt.
+///mutes are kept by character name until they expire or the server restarts.
+///players online also keep theirs when changing names, see [PlayerData::mute]
+pub struct ChatModeration {
+	config: RwLock<ChatConfig>,
+	mutes: RwLock<HashMap<String, Mute>> //lowercase names
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	last_message: Option<(Instant, String)>, //lowercase
+	mute: Option<(String, Mute)> //along with the lowercase name it was issued for
+}
+
+impl ChatModeration {
+	pub fn new(config: ChatConfig) -> Self {
+		Self {
+			config: RwLock::new(config),
+			mutes: RwLock::default()
+		}
+	}
+
+	pub async fn reload(&self, config: ChatConfig) {
+		*self.config.write().await = config;
+	}
+
+	///replaces any existing mute of the same name
+	pub async fn mute(&self, name: &str, reason: String, duration: Option<Duration>) -> Mute {
+		let mute = Mute {
+			reason,
+			expires: duration.and_then(|duration| Instant::now().checked_add(duration)) //too far ahead to ever expire otherwise
+		};
+
+		let mut mutes = self.mutes.write().await;
+		mutes.retain(|_name, mute| !mute.is_expired());
+		mutes.insert(name.to_lowercase(), mute.clone());
+		mute
+	}
+
+	///returns false if the name wasn't muted
+	pub async fn unmute(&self, name: &str) -> bool {
+		self.mutes
+			.write()
+			.await
+			.remove(&name.to_lowercase())
+			.is_some_and(|mute| !mute.is_expired())
+	}
+
+	///the mute of the name or the player, whichever applies
+	pub async fn mute_of(&self, player: &Player, name: &str) -> Option<Mute> {
+		let by_name = self.mutes
+			.read()
+			.await
+			.get(&name.to_lowercase())
+			.filter(|mute| !mute.is_expired())
+			.cloned();
+
+		if by_name.is_some() {
+			return by_name;
+		}
+
+		player.addon_data
+			.read()
+			.await
+			.chat_moderation
+			.mute
+			.as_ref()
+			.map(|(_name, mute)| mute.clone())
+			.filter(|mute| !mute.is_expired())
+	}
+
+	///keeps a mute of the old name attached to the player, so changing names doesn't lift it
+	pub async fn on_rename(&self, player: &Player, old_name: &str) {
+		let lowercase = old_name.to_lowercase();
+		let Some(mute) = self.mutes.read().await.get(&lowercase).filter(|mute| !mute.is_expired()).cloned()
+			else { return };
+
+		player.addon_data.write().await.chat_moderation.mute.get_or_insert((lowercase, mute));
+	}
+
+	///returns the text to pass on, or none if the message got blocked.
+	///the sender gets told why and the admins get to see what was blocked
+	pub async fn moderate(&self, server: &Server, source: &Player, name: &str, text: &str) -> Option<String> {
+		let verdict = match self.mute_of(source, name).await {
+			Some(mute) => Err(("muted", mute.message())),
+			None => self.check(source, text).await
+		};
+
+		match verdict {
+			Ok(text) => Some(text),
+			Err((reason, notice)) => {
+				source.notify(notice).await;
+				server.notify_admins(format!("blocked message from {name} ({reason}): {text}")).await;
+				None
+			}
+		}
+	}
+
+	///on failure returns a short reason for the admins and a notice for the sender
+	async fn check(&self, source: &Player, text: &str) -> Result<String, (&'static str, String)> {
+		let config = self.config.read().await;
+
+		let length = text.chars().count();
+		if length > config.max_length {
+			return Err(("too long", format!("your message is too long ({length}/{} characters)", config.max_length)));
+		}
+
+		if config.block_links && text.split_whitespace().any(|word| is_link(word) && !config.allowed_links.iter().any(|allowed| word.contains(allowed.as_str()))) {
+			return Err(("link", "links are not allowed here".to_owned()));
+		}
+
+		let (mut filtered, action) = filter_words(text, &config.words);
+		match action {
+			Some(WordAction::Block) => return Err(("filtered word", "your message contains a word that isn't allowed here".to_owned())),
+			Some(WordAction::Warn) => source.notify("watch your language").await,
+			Some(WordAction::Replace) | None => {}
+		}
+
+		//repetitions are checked last, so blocked attempts don't count towards them
+		let lowercase = text.to_lowercase();
+		let mut addon_data = source.addon_data.write().await;
+		let last_message = &mut addon_data.chat_moderation.last_message;
+		let window = Duration::from_secs(config.repeat_window);
+		if let Some((sent, last_text)) = last_message && *last_text == lowercase && sent.elapsed() < window {
+			return Err(("repeated", "please don't repeat yourself".to_owned()));
+		}
+		*last_message = Some((Instant::now(), lowercase));
+		drop(addon_data);
+
+		if config.caps_min_letters > 0 {
+			let letters = filtered.chars().filter(|character| character.is_alphabetic()).count();
+			let uppercase = filtered.chars().filter(|character| character.is_uppercase()).count();
+			#[expect(clippy::cast_precision_loss, reason="chat messages are way too short for this to matter")]
+			if letters >= config.caps_min_letters && uppercase as f64 >= letters as f64 * config.caps_ratio {
+				filtered = filtered.to_lowercase();
+			}
+		}
+
+		Ok(filtered)
+	}
+}
+
+impl Server {
+	///mutes by name and lets the admins as well as the target (if online) know
+	pub async fn mute(&self, name: &str, reason: String, duration: Option<Duration>, issuer: &str) {
+		let mute = self.addons.chat_moderation.mute(name, reason, duration).await;
+
+		let mut description = format!("{issuer} muted {name}");
+		if !mute.reason.is_empty() {
+			description.push_str(&format!(" ({})", mute.reason));
+		}
+		if let Some(duration) = duration {
+			description.push_str(&format!(" for {}", format_duration(duration)));
+		}
+		self.notify_admins(description).await;
+
+		if let Some(target) = self.find_player_by_name(name).await {
+			target.addon_data.write().await.chat_moderation.mute = Some((name.to_lowercase(), mute.clone()));
+			target.notify(mute.message()).await;
+		}
+	}
+
+	///lifts the mute of the name as well as those of players who were muted under it or go by it now.
+	///returns false if nothing was muted
+	pub async fn unmute(&self, name: &str, issuer: &str) -> bool {
+		let lowercase = name.to_lowercase();
+		let mut lifted = self.addons.chat_moderation.unmute(name).await;
+
+		let players = self.players.read().await.clone();
+		let mut unmuted = vec![];
+		for player in players {
+			let current_name = player.character.read().await.name.to_lowercase();
+			let mut addon_data = player.addon_data.write().await;
+			let mute = &mut addon_data.chat_moderation.mute;
+			if current_name == lowercase || mute.as_ref().is_some_and(|(muted_name, _mute)| *muted_name == lowercase) {
+				lifted |= mute.take().is_some_and(|(_name, mute)| !mute.is_expired());
+				unmuted.push(Arc::clone(&player));
+			}
+		}
+
+		if !lifted {
+			return false;
+		}
+
+		self.notify_admins(format!("{issuer} unmuted {name}")).await;
+		for player in unmuted {
+			player.notify("you are no longer muted").await;
+		}
+		true
+	}
+}
+
+///stars out replaced words and returns the most severe action any word called for
+fn filter_words(text: &str, words: &HashMap<String, WordAction>) -> (String, Option<WordAction>) {
+	let mut filtered = String::with_capacity(text.len());
+	let mut most_severe = None;
+	let mut rest = text;
+
+	while !rest.is_empty() {
+		let (word, after) = rest.split_at(rest.find(|character: char| !character.is_alphanumeric()).unwrap_or(rest.len()));
+		let action = words.get(&word.to_lowercase()).copied();
+		if action == Some(WordAction::Replace) {
+			filtered.extend(repeat_n('*', word.chars().count()));
+		} else {
+			filtered.push_str(word);
+		}
+		most_severe = most_severe.max(action);
+
+		let (separator, after) = after.split_at(after.find(char::is_alphanumeric).unwrap_or(after.len()));
+		filtered.push_str(separator);
+		rest = after;
+	}
+
+	(filtered, most_severe)
+}
+
+///catches urls as well as bare domains followed by a path, e.g. `example.com/invite`
+fn is_link(word: &str) -> bool {
+	let word = word.to_lowercase();
+	if word.contains("://") || word.starts_with("www.") {
+		return true;
+	}
+
+	word.split_once('/')
+		.and_then(|(host, _path)| host.rsplit_once('.'))
+		.is_some_and(|(domain, tld)| !domain.is_empty() && !tld.is_empty() && tld.chars().all(|character| character.is_ascii_alphabetic()))
+}
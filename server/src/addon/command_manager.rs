@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::str::SplitWhitespace;
-use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Arc;
 
-use config::{Config, ConfigError};
+use futures::future::join_all;
+use tap::Pipe;
+use tracing::info;
 
 use crate::addon::command_manager::commands::*;
 use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::addon::audit_log;
+use crate::addon::permissions::{command_permission, Issuer};
+use crate::server::config::ServerConfig;
 use crate::server::player::Player;
 use crate::server::Server;
 
@@ -18,16 +23,14 @@ pub type CommandResult = Result<Option<String>, &'static str>;
 
 pub struct CommandManager {
 	commands: HashMap<&'static str, Box<dyn CommandProxy>>,
-	admin_password: String,
-	ac_immune_password: String
+	warp: Warp //shares its locations with the registered instance
 }
 
 impl CommandManager {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
+	pub fn new(config: &ServerConfig) -> Self {
 		let mut manager = Self {
 			commands: HashMap::new(),
-			admin_password: config.get("admin_password")?,
-			ac_immune_password: config.get("ac_immune_password")?
+			warp: Warp::new(&config.warps)
 		};
 
 		manager.register(Who);
@@ -36,7 +39,7 @@ impl CommandManager {
 		manager.register(Xp);
 		manager.register(Level);
 		manager.register(Countdown);
-		manager.register(Warp::new(config)?);
+		manager.register(manager.warp.clone());
 		manager.register(Gear);
 		manager.register(Give);
 		manager.register(Kick);
@@ -45,8 +48,32 @@ impl CommandManager {
 		manager.register(Team);
 		manager.register(Act);
 		manager.register(Heal);
+		manager.register(Shutdown);
+		manager.register(Restart);
+		manager.register(Reload);
+		manager.register(Role);
+		manager.register(Ban);
+		manager.register(TempBan);
+		manager.register(IpBan);
+		manager.register(Unban);
+		manager.register(BanList);
+		manager.register(Mute);
+		manager.register(Unmute);
+		manager.register(Profile);
+		manager.register(Note);
+		manager.register(Whitelist);
+		manager.register(Audit);
+		manager.register(Report);
+		manager.register(Reports);
+		manager.register(VoteKick);
+		manager.register(Vote);
+		manager.register(Poll);
+
+		manager
+	}
 
-		Ok(manager)
+	pub async fn reload(&self, config: &ServerConfig) {
+		self.warp.reload(&config.warps).await;
 	}
 
 	pub fn register<C: Command + 'static>(&mut self, command: C) {//todo: can the lifetime be relaxed?
@@ -55,9 +82,8 @@ impl CommandManager {
 
 	pub async fn on_message<Fut: Future<Output=()>, Cb: FnOnce(CommandResult) -> Fut>(//todo: figure out lifetimes to optimize this to &str
 		&self,
-		server: &Server,
-		caller: Option<&Player>,
-		admin: bool,
+		server: &Arc<Server>,
+		issuer: Issuer<'_>,
 		text: &str,
 		command_prefix: char,
 		callback: Cb
@@ -65,14 +91,21 @@ impl CommandManager {
 		let is_command = text.starts_with(command_prefix);
 
 		if is_command {
-			let command_result = self.handle_command(server, caller, admin, text.trim_start_matches(command_prefix)).await;
+			let text = text.trim_start_matches(command_prefix);
+			let command_result = self.handle_command(server, issuer, text).await;
+			let outcome = self.is_audited(server, text).await.then(|| audit_log::outcome(&command_result));
 			callback(command_result).await;
+
+			//recorded after responding, so the log doesn't hold up the response
+			if let Some(outcome) = outcome {
+				server.audit(audit_log::describe(issuer).await, text.to_owned(), outcome).await;
+			}
 		}
 
 		is_command
 	}
 
-	async fn handle_command(&self, server: &Server, caller: Option<&Player>, admin: bool, text: &str) -> CommandResult {
+	async fn handle_command(&self, server: &Arc<Server>, issuer: Issuer<'_>, text: &str) -> CommandResult {
 		let lowercase = text.to_lowercase();
 		let mut fragments = lowercase.split_whitespace();
 
@@ -80,38 +113,56 @@ impl CommandManager {
 			.next()
 			.ok_or("no command specified (type /help for a list)")?;
 
+		info!(command = command_literal, %issuer, "command"); //parameters are left out as they might contain passwords
+
 		match command_literal {
 			//implementing these as regular command structs would effectively require inserting a reference to the command map into itself
-			"help" => self.on_help(admin),
-			"login" => self.attempt_login(caller, &mut fragments),
+			"help" => {
+				server.metrics.count_command("help").await;
+				self.on_help(server, issuer).await
+			},
+			"login" => {
+				server.metrics.count_command("login").await;
+				self.attempt_login(server, issuer.player(), &mut fragments).await
+			},
 			_ => {
-				let command = self.commands
-					.get(command_literal)
+				let (literal, command) = self.commands
+					.get_key_value(command_literal)
 					.ok_or("unknown command (type /help for a list)")?;
 
-				if command.get_admin_only() && !admin {
+				if !server.addons.permissions.allows(issuer, &command_permission(literal)).await {
 					return Err("no permission");
 				}
 
-				command.get_execution_future(server, caller, &mut fragments).await
+				server.metrics.count_command(literal).await;
+
+				command.get_execution_future(server, issuer, &mut fragments).await
 			}
 		}
 	}
 
-	#[expect(clippy::unnecessary_wraps, reason = "unsure")]
-	fn on_help(&self, admin: bool) -> CommandResult {
+	///whether `text` calls a command worth an audit log entry, see [Command::AUDITED]
+	async fn is_audited(&self, server: &Server, text: &str) -> bool {
+		let Some(literal) = text.split_whitespace().next().map(str::to_lowercase)
+			else { return false };
+		let Some(command) = self.commands.get(literal.as_str())
+			else { return false };
+
+		command.is_audited() || !server.addons.permissions.is_default(&command_permission(&literal)).await
+	}
+
+	///only lists what `issuer` is permitted to use
+	async fn on_help(&self, server: &Server, issuer: Issuer<'_>) -> CommandResult {
 		let mut message = String::new();
 		message.push_str("help");
 
 		let literals = self.commands
-			.iter()
-			.filter_map(|(literal, command)| {
-				if command.get_admin_only() && !admin {
-					return None;
-				}
-
-				Some(literal)
-			});
+			.keys()
+			.map(async |literal| server.addons.permissions.allows(issuer, &command_permission(literal)).await.then_some(*literal))
+			.pipe(join_all)
+			.await
+			.into_iter()
+			.flatten();
 
 		for command_literal in literals {//todo: there's probably a better way to do this
 			message.push_str(", ");
@@ -121,46 +172,47 @@ impl CommandManager {
 		Ok(Some(message))
 	}
 
-	fn attempt_login(&self, caller: Option<&Player>, params: &mut SplitWhitespace<'_>) -> CommandResult {
+	async fn attempt_login(&self, server: &Server, caller: Option<&Player>, params: &mut SplitWhitespace<'_>) -> CommandResult {
 		let caller = caller.ok_or(INGAME_ONLY)?;
 
 		let input = params
 			.next()
 			.ok_or("no password specified")?;
-		
-		if input == self.admin_password {
-			caller.admin.store(true, Relaxed);	
-		} else if input == self.ac_immune_password {
-			caller.ac_immune.store(true, Relaxed);
-		} else {
-			return Err("wrong password")
+
+		let roles = server.addons.permissions.login(caller, input).await;
+		if roles.is_empty() {
+			return Err("wrong password");
 		}
 
+		let name = caller.character.read().await.name.clone();
+		server.notify_admins(format!("{name} logged in as {}", roles.join(", "))).await;
+
 		Ok(Some("login successful".to_owned()))
 	}
 }
 
 pub trait Command: Send + Sync {//todo: move to commands.rs ?
+	///also determines the required permission, see [command_permission]
 	const LITERAL: &'static str;
-	const ADMIN_ONLY: bool;
+	///commands not everyone is permitted to use always get recorded in the audit log. this records the others too, for when they change the game
+	const AUDITED: bool = false;
 
-	fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> impl Future<Output=CommandResult> + Send + 'fut;//if you see an error here, ignore it -> https://github.com/intellij-rust/intellij-rust/issues/10216
+	fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> impl Future<Output=CommandResult> + Send + 'fut;//if you see an error here, ignore it -> https://github.com/intellij-rust/intellij-rust/issues/10216
 }
 
 
 //`Command` isn't object safe so we need a proxy
 trait CommandProxy: Send + Sync {//todo: Sync bound is only because of discord spaghetti {
-	fn get_admin_only(&self) -> bool;
-
-	fn get_execution_future<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandFuture<'fut>;
+	fn get_execution_future<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandFuture<'fut>;
+	fn is_audited(&self) -> bool;
 }
 
 impl<T: Command> CommandProxy for T {
-	fn get_admin_only(&self) -> bool {
-		T::ADMIN_ONLY
+	fn get_execution_future<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandFuture<'fut> {
+		Box::pin(self.execute(server, issuer, params))
 	}
 
-	fn get_execution_future<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandFuture<'fut> {
-		Box::pin(self.execute(server, caller, params))
+	fn is_audited(&self) -> bool {
+		T::AUDITED
 	}
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
 
 use protocol::nalgebra::Point3;
 
@@ -16,6 +19,17 @@ mod test;
 mod team;
 mod act;
 mod heal;
+mod shutdown;
+mod restart;
+mod reload;
+mod role;
+mod ban;
+mod mute;
+mod profile;
+mod whitelist;
+mod audit;
+mod report;
+mod vote;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct Who;
@@ -32,9 +46,9 @@ pub struct Level;
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct Countdown;
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Warp {
-	locations: HashMap<String, Point3<i64>>
+	locations: Arc<RwLock<HashMap<String, Point3<i64>>>> //shared between clones to enable reloading
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
@@ -59,4 +73,60 @@ pub struct Team;
 pub struct Act;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
-pub struct Heal;
\ No newline at end of file
+pub struct Heal;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Shutdown;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Restart;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Reload;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Role;
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Ban;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct TempBan;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct IpBan;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Unban;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct BanList;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Mute;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Unmute;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Profile;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Note;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Whitelist;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Audit;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Report;
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Reports;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct VoteKick;
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Vote;
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Poll;
@@ -1,5 +1,6 @@
 use std::str::SplitWhitespace;
 use std::time::Duration;
+use std::sync::Arc;
 use tokio::time::sleep;
 use protocol::nalgebra::Point2;
 use protocol::packet::WorldUpdate;
@@ -11,15 +12,14 @@ use protocol::utils::constants::SIZE_ZONE;
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Act;
 use crate::addon::command_manager::utils::INGAME_ONLY;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for Act {
 	const LITERAL: &'static str = "act";
-	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, _server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 		let character_guard = caller.character.read().await;
 
 		let action: &str = params
@@ -0,0 +1,42 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use tap::Pipe;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Audit;
+use crate::addon::command_manager::utils::ago;
+use crate::addon::permissions::Issuer;
+use crate::server::Server;
+
+const DEFAULT_AMOUNT: usize = 10;
+const MAX_AMOUNT: usize = 50;
+
+impl Command for Audit {
+	const LITERAL: &'static str = "audit";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let mut params = params.peekable();
+		let amount = match params.peek().map(|param| param.parse::<usize>()) {
+			Some(Ok(amount)) => {
+				params.next();
+				amount.clamp(1, MAX_AMOUNT)
+			}
+			_ => DEFAULT_AMOUNT
+		};
+		let filter = params.collect::<Vec<_>>().join(" ");
+
+		let entries = server.addons.audit_log.search(&filter, amount).await;
+		if entries.is_empty() {
+			return Ok(Some("(no matching entries)".to_owned()));
+		}
+
+		entries
+			.iter()
+			.map(|entry| format!("{} ago, {entry}", ago(entry.time)))
+			.collect::<Vec<_>>()
+			.join("\n")
+			.pipe(Some)
+			.pipe(Ok)
+	}
+}
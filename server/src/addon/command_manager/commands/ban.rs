@@ -0,0 +1,105 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ipnet::IpNet;
+use tap::Pipe;
+
+use crate::addon::bans::{self, Target};
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::{Ban, BanList, IpBan, TempBan, Unban};
+use crate::addon::audit_log;
+use crate::addon::permissions::Issuer;
+use crate::server::utils::{log_error, parse_duration};
+use crate::server::Server;
+
+impl Command for Ban {
+	const LITERAL: &'static str = "ban";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let target_query = params.next().ok_or("usage: /ban <player> [reason]")?;
+		let target = Target::name(&server.resolve_exact_name(target_query).await?);
+
+		ban(server, issuer, target, params, None).await
+	}
+}
+
+impl Command for TempBan {
+	const LITERAL: &'static str = "tempban";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		const USAGE: &str = "usage: /tempban <duration> <player> [reason]";
+
+		let duration = params.next().ok_or(USAGE)?;
+		let duration = parse_duration(duration).ok_or("invalid duration, expected something like 30m, 12h or 7d")?;
+		let target_query = params.next().ok_or(USAGE)?;
+		let target = Target::name(&server.resolve_exact_name(target_query).await?);
+
+		ban(server, issuer, target, params, Some(duration)).await
+	}
+}
+
+impl Command for IpBan {
+	const LITERAL: &'static str = "ipban";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let target_query = params.next().ok_or("usage: /ipban <player|ip|ip range> [reason]")?;
+		let target = match Target::parse_address(target_query) {
+			Some(target) => target,
+			None => server
+				.find_exact(target_query).await?
+				.address
+				.ip()
+				.pipe(IpNet::from)
+				.pipe(Target::Address)
+		};
+
+		ban(server, issuer, target, params, None).await
+	}
+}
+
+impl Command for Unban {
+	const LITERAL: &'static str = "unban";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let target_query = params.next().ok_or("usage: /unban <name|ip|ip range>")?;
+		let target = Target::parse_address(target_query).unwrap_or_else(|| Target::name(target_query));
+
+		let issuer = audit_log::describe(issuer).await;
+		match server.unban(&target, &issuer).await {
+			Ok(Some(_ban)) => Ok(Some(format!("unbanned {target}"))),
+			Ok(None) => Err("no such ban"),
+			Err(error) => {
+				log_error("saving bans", error);
+				Err("failed to save bans")
+			}
+		}
+	}
+}
+
+impl Command for BanList {
+	const LITERAL: &'static str = "banlist";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let bans = server.addons.bans.list().await;
+
+		Ok(Some(if bans.is_empty() {
+			"(no bans)".to_owned()
+		} else {
+			bans.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+		}))
+	}
+}
+
+async fn ban(server: &Server, issuer: Issuer<'_>, target: Target, params: &mut SplitWhitespace<'_>, duration: Option<Duration>) -> CommandResult {
+	let reason = params.collect::<Vec<_>>().join(" ");
+	let ban = bans::Ban::new(target, reason, audit_log::describe(issuer).await, duration);
+	let response = format!("banned {}", ban.target);
+
+	server.ban(ban).await.map_err(|error| {
+		log_error("saving bans", error);
+		"failed to save bans"
+	})?;
+
+	Ok(Some(response))
+}
@@ -1,30 +1,30 @@
 use std::str::SplitWhitespace;
 use std::time::Duration;
+use std::sync::Arc;
 
 use tokio::time::sleep;
 
-use crate::SERVER;
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Countdown;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for Countdown {
 	const LITERAL: &'static str = "countdown";
-	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, _caller: Option<&'fut Player>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let server = Arc::clone(server);
 		tokio::spawn(async move {
 			let mut count = 3;
 
 			loop {
-				SERVER.announce(char::from_digit(count, 10).unwrap()).await;
+				server.announce(char::from_digit(count, 10).unwrap()).await;
 				sleep(Duration::from_secs(1)).await;
 
 				count -= 1;
 				if count == 0 { break }
 			}
-			SERVER.announce("go!").await;
+			server.announce("go!").await;
 		});
 
 		Ok(None)
@@ -1,4 +1,5 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use protocol::packet::common::Item;
 use protocol::packet::common::item::Kind::*;
@@ -13,15 +14,15 @@ use protocol::utils::constants::rarity::LEGENDARY;
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Gear;
 use crate::addon::command_manager::utils::INGAME_ONLY;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for Gear {
 	const LITERAL: &'static str = "gear";
-	const ADMIN_ONLY: bool = false;
+	const AUDITED: bool = true;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, _server: &'fut Arc<Server>, issuer: Issuer<'fut>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 		let character = caller.character.read().await;
 
 		let items = create_items(character.occupation, character.level as i16);
@@ -1,4 +1,5 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use protocol::packet::common::{item, Item};
 use protocol::packet::world_update::Pickup;
@@ -9,15 +10,15 @@ use protocol::utils::power_of;
 use tap::Pipe;
 
 use crate::addon::command_manager::{Command, CommandResult};
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for super::Give {
     const LITERAL: &'static str = "give";
-    const ADMIN_ONLY: bool = false;
+    const AUDITED: bool = true;
 
-    async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-        let caller = caller.ok_or("ingame only")?;
+    async fn execute<'fut>(&'fut self, _server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+        let caller = issuer.player().ok_or("ingame only")?;
         
         let mut item = Item::default();
         
@@ -1,19 +1,19 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 use protocol::packet::{Hit, WorldUpdate};
 use protocol::packet::hit::Kind::Normal;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Heal;
 use crate::addon::command_manager::utils::INGAME_ONLY;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for Heal {
 	const LITERAL: &'static str = "heal";
-	const ADMIN_ONLY: bool = true;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, _server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 		let character = caller.character.read().await;
 
 		let amount: f32 = params
@@ -1,15 +1,15 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Kick;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for Kick {
 	const LITERAL: &'static str = "kick";
-	const ADMIN_ONLY: bool = true;
 
-	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		let target_query = params.next().ok_or("no target specified")?;
 		let target = server
 			.find_player(target_query).await
@@ -1,20 +1,21 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use protocol::utils::maximum_experience_of;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Level;
 use crate::addon::command_manager::utils::INGAME_ONLY;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 use crate::server::utils::give_xp;
 
 impl Command for Level {
 	const LITERAL: &'static str = "level";
-	const ADMIN_ONLY: bool = false;
+	const AUDITED: bool = true;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, _server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 
 		let target_level: i32 = params
 			.next()
@@ -0,0 +1,43 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::{Mute, Unmute};
+use crate::addon::audit_log;
+use crate::addon::permissions::Issuer;
+use crate::server::utils::parse_duration;
+use crate::server::Server;
+
+impl Command for Mute {
+	const LITERAL: &'static str = "mute";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let target_query = params.next().ok_or("usage: /mute <player> [duration] [reason]")?;
+		let name = server.resolve_exact_name(target_query).await?;
+
+		let mut params = params.peekable();
+		let duration = params.peek().and_then(|param| parse_duration(param));
+		if duration.is_some() {
+			params.next();
+		}
+		let reason = params.collect::<Vec<_>>().join(" ");
+
+		server.mute(&name, reason, duration, &audit_log::describe(issuer).await).await;
+
+		Ok(Some(format!("muted {name}")))
+	}
+}
+
+impl Command for Unmute {
+	const LITERAL: &'static str = "unmute";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let target_query = params.next().ok_or("usage: /unmute <player>")?;
+		let name = server.resolve_exact_name(target_query).await?;
+
+		server
+			.unmute(&name, &audit_log::describe(issuer).await).await
+			.then(|| Some(format!("unmuted {name}")))
+			.ok_or("that player isn't muted")
+	}
+}
@@ -1,18 +1,18 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use protocol::utils::constants::combat_classes::*;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Player as PlayerCommand;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for PlayerCommand {
 	const LITERAL: &'static str = "player";
-	const ADMIN_ONLY: bool = false;
 
 	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
-	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		let player = server
 			.find_player(params.next().ok_or("no target specified")?).await
 			.ok_or("target not found")?;
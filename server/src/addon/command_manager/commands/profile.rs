@@ -0,0 +1,94 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::{Note, Profile};
+use crate::addon::command_manager::utils::ago;
+use crate::addon::profiles;
+use crate::addon::audit_log;
+use crate::addon::permissions::Issuer;
+use crate::server::utils::{format_duration, log_error, unix_now};
+use crate::server::Server;
+
+impl Command for Profile {
+	const LITERAL: &'static str = "profile";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let target_query = params.next().ok_or("usage: /profile <player>")?;
+		let name = server.resolve_exact_name(target_query).await?;
+		let online = server.find_player_by_name(&name).await.is_some();
+
+		let profile = server
+			.profile_of(&name).await
+			.map_err(|error| {
+				log_error("load-profile", error);
+				"failed to load profile"
+			})?
+			.ok_or("no such profile")?;
+
+		let mut lines = vec![format!(
+			"{}: first seen {} ago, {}, played {}",
+			profile.name,
+			ago(profile.first_seen),
+			if online { "online now".to_owned() } else { format!("last seen {} ago", ago(profile.last_seen)) },
+			format_duration(Duration::from_secs(profile.play_time))
+		)];
+
+		if let Some(progress) = profile.progression.last() {
+			lines.push(format!("level {} {} ({})", progress.level, progress.class, progress.specialization));
+		}
+
+		lines.push(format!("addresses: {}", profile.addresses.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")));
+
+		let alts = server.addons.profiles
+			.all().await
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|other| !other.name.eq_ignore_ascii_case(&profile.name) && !other.addresses.is_disjoint(&profile.addresses))
+			.map(|other| other.name)
+			.collect::<Vec<_>>();
+		if !alts.is_empty() {
+			lines.push(format!("also seen as: {}", alts.join(", ")));
+		}
+
+		for note in &profile.notes {
+			lines.push(format!("note by {} {} ago: {}", note.author, ago(note.time), note.text));
+		}
+
+		Ok(Some(lines.join("\n")))
+	}
+}
+
+impl Command for Note {
+	const LITERAL: &'static str = "note";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		const USAGE: &str = "usage: /note <player> <text>";
+
+		let target_query = params.next().ok_or(USAGE)?;
+		let text = params.collect::<Vec<_>>().join(" ");
+		if text.is_empty() {
+			return Err(USAGE);
+		}
+
+		let name = server.resolve_exact_name(target_query).await?;
+		let note = profiles::Note {
+			time: unix_now(),
+			author: audit_log::describe(issuer).await,
+			text
+		};
+
+		let found = server
+			.edit_profile(&name, |profile| profile.notes.push(note))
+			.await
+			.map_err(|error| {
+				log_error("save-profile", error);
+				"failed to save profile"
+			})?;
+
+		found
+			.then(|| Some(format!("noted on {name}")))
+			.ok_or("no such profile")
+	}
+}
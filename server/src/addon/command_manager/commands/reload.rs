@@ -0,0 +1,20 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Reload;
+use crate::addon::permissions::Issuer;
+use crate::server::Server;
+
+impl Command for Reload {
+	const LITERAL: &'static str = "reload";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let response = match server.reload_config().await {
+			Ok(()) => "config reloaded".to_owned(),
+			Err(error) => format!("config unchanged, reload failed: {error}")
+		};
+
+		Ok(Some(response))
+	}
+}
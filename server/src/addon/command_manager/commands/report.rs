@@ -0,0 +1,60 @@
+use std::ptr;
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::{Report, Reports};
+use crate::addon::command_manager::utils::{ago, INGAME_ONLY};
+use crate::addon::permissions::Issuer;
+use crate::server::Server;
+
+const DEFAULT_AMOUNT: usize = 5;
+const MAX_AMOUNT: usize = 20;
+
+impl Command for Report {
+	const LITERAL: &'static str = "report";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
+		let target_query = params.next().ok_or("usage: /report <player> <reason>")?;
+		let reason = params.collect::<Vec<_>>().join(" ");
+		if reason.is_empty() {
+			return Err("please include a reason");
+		}
+
+		let target = server.find_exact(target_query).await?;
+		if ptr::eq(target.as_ref(), caller) {
+			return Err("you can't report yourself");
+		}
+
+		let message = match server.report(caller, &target, reason).await {
+			Ok(report) => format!("thanks, the moderators got your report (#{})", report.id),
+			Err(refusal) => refusal
+		};
+		Ok(Some(message))
+	}
+}
+
+impl Command for Reports {
+	const LITERAL: &'static str = "reports";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let amount = params
+			.next()
+			.map(|amount| amount.parse::<usize>().map_err(|_| "usage: /reports [amount]"))
+			.transpose()?
+			.unwrap_or(DEFAULT_AMOUNT)
+			.clamp(1, MAX_AMOUNT);
+
+		let reports = server.addons.reports.latest(amount).await;
+		if reports.is_empty() {
+			return Ok(Some("(no reports)".to_owned()));
+		}
+
+		Ok(Some(reports
+			.iter()
+			.map(|report| format!("{} ago, {report}", ago(report.time)))
+			.collect::<Vec<_>>()
+			.join("\n")))
+	}
+}
@@ -0,0 +1,17 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Restart;
+use crate::addon::command_manager::commands::shutdown::schedule;
+use crate::addon::permissions::Issuer;
+use crate::server::shutdown::Outcome;
+use crate::server::Server;
+
+impl Command for Restart {
+	const LITERAL: &'static str = "restart";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		schedule(server, params, Outcome::Restart).await
+	}
+}
@@ -0,0 +1,63 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Role;
+use crate::addon::permissions::Issuer;
+use crate::server::Server;
+
+const USAGE: &str = "usage: /role [add|remove <role>] <player>";
+
+impl Command for Role {
+	const LITERAL: &'static str = "role";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let permissions = &server.addons.permissions;
+
+		let Some(first) = params.next()
+			else {
+				return Ok(Some(format!("roles: {}\n{USAGE}", permissions.role_names().await.join(", "))));
+			};
+
+		let grant = match first {
+			"add"    => true,
+			"remove" => false,
+			query => {
+				let target = server.find_exact(query).await?;
+				let roles = permissions.roles_of(&target).await;
+				let name = &target.character.read().await.name;
+
+				return Ok(Some(if roles.is_empty() {
+					format!("{name} has no roles")
+				} else {
+					format!("{name} has {}", roles.join(", "))
+				}));
+			}
+		};
+
+		let role = params.next().ok_or(USAGE)?;
+		let target_query = params.next().ok_or(USAGE)?;
+		let target = server.find_exact(target_query).await?;
+
+		if grant {
+			permissions.grant(&target, role).await?;
+			let mut profile = target.profile.write().await;
+			profile.roles.insert(role.to_owned());
+			profile.trusted_addresses.insert(target.address.ip());
+		} else {
+			permissions.revoke(&target, role).await?;
+			let mut profile = target.profile.write().await;
+			profile.roles.remove(role);
+			if profile.roles.is_empty() {
+				profile.trusted_addresses.clear();
+			}
+		}
+
+		let name = target.character.read().await.name.clone();
+		let message = if grant { format!("{name} is now {role}") } else { format!("{name} is no longer {role}") };
+		target.notify(message.clone()).await;
+		server.notify_admins(message.clone()).await;
+
+		Ok(Some(message))
+	}
+}
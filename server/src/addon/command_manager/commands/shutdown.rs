@@ -0,0 +1,50 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Shutdown;
+use crate::addon::permissions::Issuer;
+use crate::server::shutdown::Outcome;
+use crate::server::Server;
+
+const DEFAULT_DELAY: u64 = 10;
+const MAX_DELAY: u64 = 24 * 60 * 60; //longer would overflow eventually, and nobody waits that long anyway
+
+impl Command for Shutdown {
+	const LITERAL: &'static str = "shutdown";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		schedule(server, params, Outcome::Exit).await
+	}
+}
+
+///`[seconds] [reason]` or `cancel`
+pub(super) async fn schedule(server: &Arc<Server>, params: &mut SplitWhitespace<'_>, outcome: Outcome) -> CommandResult {
+	let delay = match params.next() {
+		Some("cancel") => {
+			return server
+				.cancel_shutdown()
+				.await
+				.ok_or("no shutdown scheduled")
+				.map(|()| None);
+		}
+		Some(seconds) => seconds.parse().map_err(|_| "invalid delay")?,
+		None => DEFAULT_DELAY
+	};
+	if delay > MAX_DELAY {
+		return Err("delay too long, at most 1 day is allowed");
+	}
+
+	let reason = params
+		.collect::<Vec<_>>()
+		.join(" ");
+
+	server.schedule_shutdown(
+		Duration::from_secs(delay),
+		(!reason.is_empty()).then_some(reason),
+		outcome
+	).await;
+
+	Ok(None)
+}
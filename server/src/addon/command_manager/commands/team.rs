@@ -1,18 +1,19 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Team;
 use crate::addon::command_manager::utils::INGAME_ONLY;
 use crate::addon::pvp;
+use crate::addon::permissions::Issuer;
 use crate::server::player::Player;
 use crate::server::Server;
 
 impl Command for Team {
 	const LITERAL: &'static str = "team";
-	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 
 		let Some(param) = params.next()
 			else {
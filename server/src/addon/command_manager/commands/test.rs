@@ -1,5 +1,6 @@
 use std::ops::{Div as _, Mul as _, Sub as _};
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use protocol::packet::creature_update::{Affiliation, Appearance, AppearanceFlag};
 use protocol::packet::{CreatureUpdate, StatusEffect};
@@ -20,16 +21,15 @@ use tap::{Pipe, Tap};
 use crate::addon::{command_manager::{Command, CommandResult}, models, play_sound_at_player};
 use crate::addon::command_manager::commands::Test;
 use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::addon::permissions::Issuer;
 use crate::server::player::Player;
 use crate::server::Server;
-use crate::SERVER;
 
 impl Command for Test {
 	const LITERAL: &'static str = "t";
-	const ADMIN_ONLY: bool = true;
 
-	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 
 		match params.next() {
 			Some("check") => checkerboard(server, caller).await,
@@ -42,7 +42,7 @@ impl Command for Test {
 			Some("s") => play_sound(caller, params).await,
 			Some("model") => model(params, server, caller).await?,
 			Some("shield") => shield(caller).await,
-			Some("gallery") => gallery(caller).await,
+			Some("gallery") => gallery(server, caller).await,
 			Some(_) => { return Err("unknown sub-command") }
 			None => { return Err("too few arguments") },
 		}
@@ -280,7 +280,7 @@ async fn model(params: &mut SplitWhitespace<'_>, server: &Server, caller: &Playe
 		else {
 			return Err("no file name specified");
 		};
-	let mut blocks = models::parse_model(file);
+	let mut blocks = models::parse_model(file).map_err(|_| "failed to parse the model")?;
 
 	let offset = caller
 		.character
@@ -313,7 +313,7 @@ async fn shield(caller: &Player) {
 	caller.send_ignoring(&WorldUpdate::from(se)).await;
 }
 
-async fn gallery(caller: &Player) {
+async fn gallery(server: &Server, caller: &Player) {
 	let pos = caller.character.read().await.position;
 	let spacing = SIZE_BLOCK * 3;
 	
@@ -344,7 +344,7 @@ async fn gallery(caller: &Player) {
 			name: Some(id.to_string()),
 			..Default::default()
 		}
-			.pipe_ref(|cu| SERVER.broadcast(cu, None))
+			.pipe_ref(|cu| server.broadcast(cu, None))
 			.await;
 	}
 }
\ No newline at end of file
@@ -0,0 +1,63 @@
+use std::ptr;
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::{Poll, Vote, VoteKick};
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::addon::voting::Subject;
+use crate::addon::permissions::Issuer;
+use crate::server::Server;
+
+impl Command for VoteKick {
+	const LITERAL: &'static str = "votekick";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
+		let target = server.find_exact(params.next().ok_or("usage: /votekick <player> [reason]")?).await?;
+		if ptr::eq(target.as_ref(), caller) {
+			return Err("you can't vote-kick yourself");
+		}
+
+		let subject = Subject::Kick {
+			name: target.character.read().await.name.clone(),
+			reason: params.collect::<Vec<_>>().join(" ")
+		};
+		server.start_vote(caller, subject).await?;
+
+		Ok(None)
+	}
+}
+
+impl Command for Poll {
+	const LITERAL: &'static str = "poll";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
+		let question = params.collect::<Vec<_>>().join(" ");
+		if question.is_empty() {
+			return Err("usage: /poll <yes/no question>");
+		}
+
+		server.start_vote(caller, Subject::Poll(question)).await?;
+
+		Ok(None)
+	}
+}
+
+impl Command for Vote {
+	const LITERAL: &'static str = "vote";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
+		let yes = match params.next() {
+			Some("yes") => true,
+			Some("no") => false,
+			_ => return Err("usage: /vote yes|no")
+		};
+
+		server.cast_vote(caller, yes).await?;
+
+		Ok(Some(format!("voted {}", if yes { "yes" } else { "no" })))
+	}
+}
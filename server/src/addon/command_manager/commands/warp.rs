@@ -1,41 +1,40 @@
 use std::str::SplitWhitespace;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use config::{ConfigError, Config};
 use tap::Pipe;
+use tokio::sync::RwLock;
 use protocol::nalgebra::Point3;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Warp;
 use crate::addon::command_manager::utils::INGAME_ONLY;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Warp {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
-		let instance = Self {
-			locations: config
-				.get::<HashMap<String, Point3<i64>>>("warps")?
-				.into_iter()
-				.map(|(name, coords)| (name.to_lowercase(), coords))
-				.collect()
-		};
-
-		Ok(instance)
+	pub fn new(locations: &HashMap<String, Point3<i64>>) -> Self {
+		Self {
+			locations: Arc::new(RwLock::new(normalize(locations)))
+		}
+	}
+
+	pub async fn reload(&self, locations: &HashMap<String, Point3<i64>>) {
+		*self.locations.write().await = normalize(locations);
 	}
 }
 
 impl Command for Warp {
 	const LITERAL: &'static str = "warp";
-	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
+
+		let locations = self.locations.read().await;
 
 		let Some(destination) = params.next()
 			else {
-				return self
-					.locations
+				return locations
 					.keys()
 					.map(String::as_str)
 					.intersperse(", ")
@@ -45,12 +44,20 @@ impl Command for Warp {
 					.pipe(Ok)
 			};
 
-		let coordinates = self.locations
+		let coordinates = *locations
 			.get(destination)
 			.ok_or("unkown destination (type /warp for a list)")?;
+		drop(locations);
 
-		server.teleport(caller, *coordinates).await;
+		server.teleport(caller, coordinates).await;
 
 		Ok(None)
 	}
-}
\ No newline at end of file
+}
+
+fn normalize(locations: &HashMap<String, Point3<i64>>) -> HashMap<String, Point3<i64>> {
+	locations
+		.iter()
+		.map(|(name, coords)| (name.to_lowercase(), *coords))
+		.collect()
+}
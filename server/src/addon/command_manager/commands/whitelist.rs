@@ -0,0 +1,63 @@
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::addon::bans::Target;
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Whitelist;
+use crate::addon::audit_log;
+use crate::addon::permissions::Issuer;
+use crate::server::utils::log_error;
+use crate::server::Server;
+
+const USAGE: &str = "usage: /whitelist on|off|list or /whitelist add|remove <name|ip|ip range>";
+
+impl Command for Whitelist {
+	const LITERAL: &'static str = "whitelist";
+
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let whitelist = &server.addons.whitelist;
+
+		let Some(action) = params.next()
+			else {
+				let state = if whitelist.is_enabled().await { "enabled" } else { "disabled" };
+				return Ok(Some(format!("the whitelist is {state}\n{USAGE}")));
+			};
+
+		let (changed, message) = match action {
+			"on" | "off" => {
+				let enabled = action == "on";
+				let verb = if enabled { "enabled" } else { "disabled" };
+				(whitelist.set_enabled(enabled).await, format!("{verb} the whitelist"))
+			}
+			"add" | "remove" => {
+				let query = params.next().ok_or(USAGE)?;
+				let entry = Target::parse_address(query).unwrap_or_else(|| Target::name(query));
+				if action == "add" {
+					(whitelist.add(entry.clone()).await, format!("added {entry} to the whitelist"))
+				} else {
+					(whitelist.remove(&entry).await, format!("removed {entry} from the whitelist"))
+				}
+			}
+			"list" => {
+				let entries = whitelist.entries().await;
+				return Ok(Some(if entries.is_empty() {
+					"(the whitelist is empty)".to_owned()
+				} else {
+					entries.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+				}));
+			}
+			_ => return Err(USAGE)
+		};
+
+		let changed = changed.map_err(|error| {
+			log_error("saving whitelist", error);
+			"failed to save whitelist"
+		})?;
+		if !changed {
+			return Err("nothing changed");
+		}
+
+		server.notify_admins(format!("{} {message}", audit_log::describe(issuer).await)).await;
+		Ok(Some(message))
+	}
+}
@@ -1,27 +1,29 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use futures::future::join_all;
 use tap::Pipe;
 
+use crate::addon::afk::is_afk;
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::{Who, WhoIp};
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 
 impl Command for Who {
 	const LITERAL: &'static str = "who";
-	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		server
 			.players
 			.read()
 			.await
 			.iter()
 			.map(async |player| format!(
-				"#{} {}",
+				"#{} {}{}",
 				player.id.0,
-				&player.character.read().await.name
+				&player.character.read().await.name,
+				if is_afk(player).await { " (afk)" } else { "" }
 			))
 			.pipe(join_all)
 			.await
@@ -39,9 +41,8 @@ impl Command for Who {
 
 impl Command for WhoIp {
 	const LITERAL: &'static str = "who_ip";
-	const ADMIN_ONLY: bool = true;
 
-	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Arc<Server>, _issuer: Issuer<'fut>, _params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		server
 			.players
 			.read()
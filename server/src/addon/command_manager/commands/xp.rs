@@ -1,18 +1,19 @@
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Xp;
 use crate::addon::command_manager::utils::INGAME_ONLY;
-use crate::server::player::Player;
+use crate::addon::permissions::Issuer;
 use crate::server::Server;
 use crate::server::utils::give_xp;
 
 impl Command for Xp {
 	const LITERAL: &'static str = "xp";
-	const ADMIN_ONLY: bool = false;
+	const AUDITED: bool = true;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
-		let caller = caller.ok_or(INGAME_ONLY)?;
+	async fn execute<'fut>(&'fut self, _server: &'fut Arc<Server>, issuer: Issuer<'fut>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = issuer.player().ok_or(INGAME_ONLY)?;
 
 		let amount: i32 = params
 			.next()
@@ -1,5 +1,10 @@
 use std::sync::Arc;
+use std::time::Duration;
+
+use protocol::packet::common::CreatureId;
+
 use crate::server::player::Player;
+use crate::server::utils::{format_duration, unix_now};
 use crate::server::Server;
 
 pub const INGAME_ONLY: &str = "this command can only be used ingame";
@@ -28,4 +33,56 @@ impl Server {
 
 		None
 	}
-}
\ No newline at end of file
+
+	///exact (case insensitive) matches only, unlike [Self::find_player]
+	pub async fn find_player_by_name(&self, name: &str) -> Option<Arc<Player>> {
+		let players = self.players.read().await.clone();
+		for player in players {
+			if player.character.read().await.name.eq_ignore_ascii_case(name) {
+				return Some(player);
+			}
+		}
+		None
+	}
+
+	///`#id` or the full name (case insensitive). unlike [Self::find_player] this never picks someone else by accident
+	pub async fn find_exact(&self, query: &str) -> Result<Arc<Player>, &'static str> {
+		if let Some(id) = query.strip_prefix('#') {
+			let id = id.parse().map_err(|_| "invalid id")?;
+			return self.find_player_by_id(CreatureId(id)).await.ok_or("target not found");
+		}
+
+		let mut matches = self.players_named(query).await;
+		match matches.len() {
+			0 => Err("target not found"),
+			1 => Ok(matches.remove(0)),
+			_ => Err("several players go by that name, use their #id instead")
+		}
+	}
+
+	///like [Self::find_exact], but names that nobody online goes by are taken as the name of an offline player
+	pub async fn resolve_exact_name(&self, query: &str) -> Result<String, &'static str> {
+		if !query.starts_with('#') && self.players_named(query).await.is_empty() {
+			return Ok(query.to_owned());
+		}
+
+		Ok(self.find_exact(query).await?.character.read().await.name.clone())
+	}
+
+	async fn players_named(&self, name: &str) -> Vec<Arc<Player>> {
+		let players = self.players.read().await.clone();
+
+		let mut matches = vec![];
+		for player in players {
+			if player.character.read().await.name.eq_ignore_ascii_case(name) {
+				matches.push(player);
+			}
+		}
+		matches
+	}
+}
+
+///how long ago a unix timestamp (in seconds) was
+pub fn ago(time: u64) -> String {
+	format_duration(Duration::from_secs(unix_now().saturating_sub(time)))
+}
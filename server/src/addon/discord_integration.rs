@@ -1,10 +1,11 @@
+use std::sync::Arc;
 use std::time::Duration;
 
-use config::{Config, ConfigError};
 use itertools::Itertools;
 use tap::Pipe;
 use tokio::sync::mpsc;
 use tokio::time::sleep;
+use tracing::{error, warn};
 use twilight_gateway::{EventTypeFlags, Shard, StreamExt};
 use twilight_http::Client;
 use twilight_model::gateway::{Intents, ShardId};
@@ -14,14 +15,19 @@ use twilight_model::id::Id;
 
 use protocol::packet::ChatMessageFromServer;
 use protocol::packet::common::CreatureId;
-use crate::SERVER;
 use crate::{addon::command_manager::CommandResult, server::utils::log_error};
-use crate::server::utils::extend_lifetime;
 
+use crate::addon::permissions::Issuer;
+use crate::server::config::ServerConfig;
 use crate::server::Server;
 
 #[derive(Debug)]
 pub struct DiscordIntegration {
+	connection: Option<Connection> //none if no bot token is configured
+}
+
+#[derive(Debug)]
+struct Connection {
 	token: String,
 	public_channel: Id<ChannelMarker>,
 	admin_channel: Id<ChannelMarker>,
@@ -29,15 +35,17 @@ pub struct DiscordIntegration {
 }
 
 impl DiscordIntegration {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
-		let token = config.get_string("discord_bot_token")?;
+	///expects the config to be validated, as that guarantees the channel ids to be present and non-zero
+	pub fn new(config: &ServerConfig) -> Self {
+		let Some(token) = config.discord_bot_token.clone()
+			else { return Self { connection: None } };
 
 		let (tx, mut rx) = mpsc::channel(1000);
 		let http = Client::new(token.clone());
-		let public_channel = config.get::<u64>("discord_public_channel_id")?.pipe(Id::new);
-		let  admin_channel = config.get::<u64>( "discord_admin_channel_id")?.pipe(Id::new);
+		let public_channel = config.discord_public_channel_id.expect("validated").pipe(Id::new);
+		let  admin_channel = config.discord_admin_channel_id .expect("validated").pipe(Id::new);
 		
-		let instance = Self {
+		let connection = Connection {
 			token,
 			public_channel,
 			admin_channel,
@@ -58,7 +66,7 @@ impl DiscordIntegration {
 			}
 		});
 
-		Ok(instance)
+		Self { connection: Some(connection) }
 	}
 }
 
@@ -78,14 +86,18 @@ async fn bulk_post(http: &Client, messages: &[(String, bool)], admin: bool, chan
 }
 
 impl DiscordIntegration {
-	pub fn run(&self) {
+	pub fn run(&self, server: Arc<Server>) {
+		let Some(connection) = &self.connection
+			else { return };
+
 		let mut shard = Shard::new(
 			ShardId::ONE,
-			self.token.clone(),
+			connection.token.clone(),
 			Intents::GUILD_MESSAGES | Intents::MESSAGE_CONTENT
 		);
 
-		let self_static = extend_lifetime(self);
+		let admin_channel = connection.admin_channel;
+		let public_channel = connection.public_channel;
 
 		tokio::spawn(async move {
 			loop {
@@ -95,20 +107,19 @@ impl DiscordIntegration {
 					Ok(MessageCreate(message)) => {
 						let channel_id = message.channel_id.get();
 
-						let admin = if channel_id == self_static.admin_channel {
+						let admin = if channel_id == admin_channel {
 							true
-						} else if channel_id == self_static.public_channel {
+						} else if channel_id == public_channel {
 							false
 						} else {
 							continue
 						};
 
-						let callback = |response| { Self::command_callback(&SERVER, response, admin) };
+						let callback = |response| { Self::command_callback(&server, response, admin) };
 
-						let is_command = SERVER.addons.command_manager.on_message(
-							&SERVER,
-							None,
-							admin,
+						let is_command = server.addons.command_manager.on_message(
+							&server,
+							Issuer::Discord { admin_channel: admin, user: &message.author.name },
 							&message.content,
 							'.',
 							callback
@@ -118,23 +129,25 @@ impl DiscordIntegration {
 							continue;
 						}
 
-						SERVER.broadcast(&ChatMessageFromServer {//dont use server.announce() as that would cause an echo
+						server.broadcast(&ChatMessageFromServer {//dont use server.announce() as that would cause an echo
 							source: CreatureId(0),
 							text: format!("<{}> {}", message.author.name, message.content)
 						}, None).await;
 					},
 
-					Ok(event) => { dbg!("unexpected discord event", event); },
+					Ok(event) => warn!(kind = ?event.kind(), "unexpected discord event"),
 
-					#[expect(clippy::dbg_macro, reason = "keeping this until i figure out the errors")]
-					Err(error) => { dbg!(&error); } // todo: proper error handling
+					Err(error) => error!(%error, "failed to receive discord event")
 				}
 			}
 		});
 	}
 
 	pub async fn post(&self, message: &str, admin: bool) {
-		self.queue
+		let Some(connection) = &self.connection
+			else { return };
+
+		connection.queue
 			.send((message.into(), admin))
 			.await
 			.unwrap();
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+
+use config::ConfigError;
+use serde::Deserialize;
+
+use protocol::Packet;
+use protocol::packet::{self, *};
+use protocol::packet::area_request::{Region, Zone};
+
+use crate::server::config::invalid;
+use crate::server::player::Player;
+use crate::server::token_bucket::TokenBucket;
+use crate::server::Server;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitedPacket {
+	Chat,
+	Hit,
+	Projectile,
+	CreatureUpdate,
+	AreaRequest
+}
+
+impl LimitedPacket {
+	pub const ALL: [Self; 5] = [Self::Chat, Self::Hit, Self::Projectile, Self::CreatureUpdate, Self::AreaRequest];
+
+	fn of(packet_id: &packet::Id) -> Option<Self> {
+		match *packet_id {
+			ChatMessageFromClient::ID => Some(Self::Chat),
+			Hit                  ::ID => Some(Self::Hit),
+			Projectile           ::ID => Some(Self::Projectile),
+			CreatureUpdate       ::ID => Some(Self::CreatureUpdate),
+			AreaRequest::<Zone>  ::ID |
+			AreaRequest::<Region>::ID => Some(Self::AreaRequest),
+			_ => None
+		}
+	}
+
+	///same as the respective key in the config file
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Chat           => "chat",
+			Self::Hit            => "hit",
+			Self::Projectile     => "projectile",
+			Self::CreatureUpdate => "creature_update",
+			Self::AreaRequest    => "area_request"
+		}
+	}
+
+	const fn description(self) -> &'static str {
+		match self {
+			Self::Chat           => "chat messages",
+			Self::Hit            => "hits",
+			Self::Projectile     => "projectiles",
+			Self::CreatureUpdate => "character updates",
+			Self::AreaRequest    => "map requests"
+		}
+	}
+}
+
+///counters for the metrics endpoint
+#[derive(Debug, Default)]
+pub struct FloodStatistics {
+	pub dropped: HashMap<LimitedPacket, AtomicU64>,
+	pub warnings: AtomicU64,
+	pub kicks: AtomicU64
+}
+
+///rate limits packets per player. exceeding a limit escalates from a warning to dropping packets to a kick
+pub struct FloodProtection {
+	limits: PacketLimits,
+	pub statistics: FloodStatistics
+}
+
+impl FloodProtection {
+	pub fn new(limits: PacketLimits) -> Self {
+		Self {
+			limits,
+			statistics: FloodStatistics {
+				dropped: LimitedPacket::ALL.map(|kind| (kind, AtomicU64::default())).into(),
+				..Default::default()
+			}
+		}
+	}
+
+	///returns false if the packet should be discarded
+	pub async fn allows(&self, server: &Server, source: &Player, packet_id: &packet::Id) -> bool {
+		let Some(kind) = LimitedPacket::of(packet_id)
+			else { return true };
+
+		let mut addon_data = source.addon_data.write().await;
+		let player_data = &mut addon_data.flood_protection;
+
+		let limit = self.limits.of(kind);
+		if player_data
+			.buckets
+			.entry(kind)
+			.or_insert_with(|| TokenBucket::new(limit.burst.into(), limit.per_second))
+			.try_take()
+		{
+			return true;
+		}
+
+		//every dropped packet costs patience, which slowly recovers over time
+		let patience = player_data
+			.patience
+			.get_or_insert_with(|| TokenBucket::new(self.limits.kick_after.into(), 1.0));
+		let first_offense = patience.is_full();
+		let patience_left = patience.try_take();
+		drop(addon_data);
+
+		self.statistics.dropped[&kind].fetch_add(1, Relaxed);
+
+		if !patience_left {
+			self.statistics.kicks.fetch_add(1, Relaxed);
+			server.kick(source, format!("flooding ({})", kind.name())).await;
+		} else if first_offense {
+			self.statistics.warnings.fetch_add(1, Relaxed);
+			source.notify(format!("you are sending {} too quickly, excess ones will be ignored", kind.description())).await;
+		}
+
+		false
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	buckets: HashMap<LimitedPacket, TokenBucket>,
+	patience: Option<TokenBucket>
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PacketLimits {
+	kick_after: u32,
+	chat: Limit,
+	hit: Limit,
+	projectile: Limit,
+	creature_update: Limit,
+	area_request: Limit
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Limit {
+	burst: u32,
+	per_second: f64
+}
+
+impl Default for PacketLimits {
+	fn default() -> Self {
+		Self {
+			kick_after: 50,
+			chat: Limit { burst: 5, per_second: 0.5 },
+			hit: Limit { burst: 100, per_second: 50.0 },
+			projectile: Limit { burst: 50, per_second: 20.0 },
+			creature_update: Limit { burst: 200, per_second: 100.0 },
+			area_request: Limit { burst: 500, per_second: 100.0 }
+		}
+	}
+}
+
+impl PacketLimits {
+	const fn of(&self, kind: LimitedPacket) -> Limit {
+		match kind {
+			LimitedPacket::Chat           => self.chat,
+			LimitedPacket::Hit            => self.hit,
+			LimitedPacket::Projectile     => self.projectile,
+			LimitedPacket::CreatureUpdate => self.creature_update,
+			LimitedPacket::AreaRequest    => self.area_request
+		}
+	}
+
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.kick_after < 1 {
+			return invalid("packet_limits.kick_after must be at least 1");
+		}
+		for kind in LimitedPacket::ALL {
+			let limit = self.of(kind);
+			if limit.burst < 1 {
+				return invalid(format!("packet_limits.{}.burst must be at least 1", kind.name()));
+			}
+			if !(limit.per_second > 0.0 && limit.per_second.is_finite()) {
+				return invalid(format!("packet_limits.{}.per_second must be positive", kind.name()));
+			}
+		}
+		Ok(())
+	}
+}
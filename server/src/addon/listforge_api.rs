@@ -1,49 +1,64 @@
 use std::env::consts::OS;
 use std::net::Ipv6Addr;
+use std::sync::Arc;
 use std::time::{Instant, Duration};
 
 use axum::{Router, Json};
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::extract::State;
-use config::{Config, ConfigError};
+use axum::http::header::CONTENT_TYPE;
 use futures::future::join_all;
 use serde::Serialize;
 use tap::Pipe;
 use tokio::net::TcpListener;
 
-use crate::SERVER;
+use crate::server::config::ServerConfig;
+use crate::server::Server;
+
+mod admin;
 
 pub struct ListforgeApi {
 	slots: i32,
 	name: String,
 	discord: String,
-	port: u16
+	port: Option<u16>, //none if the api is disabled
+	admin_api_token: Option<String> //none if the admin api is disabled
 }
 
 impl ListforgeApi {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
+	pub fn new(config: &ServerConfig) -> Self {
 		Self {
-			slots: config.get("slots")?,
-			name: config.get("name")?,
-			discord: config.get("discord_invite")?,
-			port: config.get("listforgeapi_port")?
-		}.pipe(Ok)
+			slots: config.slots,
+			name: config.name.clone(),
+			discord: config.discord_invite.clone(),
+			port: config.listforgeapi_port,
+			admin_api_token: config.admin_api_token.clone()
+		}
 	}
 
-	pub async fn run(&self) {
+	pub async fn run(&self, server: Arc<Server>) {
+		let Some(port) = self.port
+			else { return };
+
 		let state = (
+			Arc::clone(&server),
 			Instant::now(),
 			self.slots,
 			self.name.clone(),
 			self.discord.clone()
 		);
 
-		let router = Router::new()
+		let mut router = Router::new()
 			.route("/api/info", get(info))
+			.route("/metrics", get(metrics))
 			.with_state(state);
 
-		let listener = TcpListener::bind((Ipv6Addr::UNSPECIFIED, self.port))
+		if let Some(token) = &self.admin_api_token {
+			router = router.nest("/api/admin", admin::router(server, token));
+		}
+
+		let listener = TcpListener::bind((Ipv6Addr::UNSPECIFIED, port))
 			.await
 			.expect("failed to bind API socket");
 
@@ -55,11 +70,11 @@ impl ListforgeApi {
 	}
 }
 
-async fn info(State((startup_time, slots, name, discord)): State<(Instant, i32, String, String)>) -> impl IntoResponse {
+async fn info(State((server, startup_time, slots, name, discord)): State<(Arc<Server>, Instant, i32, String, String)>) -> impl IntoResponse {
 	Info {
-		players: get_all_player_names().await,
+		players: get_all_player_names(&server).await,
 		platform: OS.into(),
-		mapseed: SERVER.mapseed,
+		mapseed: server.mapseed,
 		uptime: startup_time.elapsed(),
 		slots,
 		name,
@@ -67,6 +82,12 @@ async fn info(State((startup_time, slots, name, discord)): State<(Instant, i32,
 	}.pipe(Json)
 }
 
+async fn metrics(State((server, ..)): State<(Arc<Server>, Instant, i32, String, String)>) -> impl IntoResponse {
+	let headers = [(CONTENT_TYPE, "text/plain; version=0.0.4")];
+
+	(headers, server.render_metrics().await)
+}
+
 #[derive(Serialize)]
 struct Info {
 	players: Vec<String>,
@@ -78,8 +99,8 @@ struct Info {
 	discord: String
 }
 
-async fn get_all_player_names() -> Vec<String> {
-	SERVER
+async fn get_all_player_names(server: &Server) -> Vec<String> {
+	server
 		.players
 		.read()
 		.await
@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use axum::{middleware, Json, Router};
+use axum::extract::{Path, Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tap::Pipe;
+
+use protocol::nalgebra::Point3;
+
+use crate::addon::bans::{Ban, Target};
+use crate::addon::permissions::Issuer;
+use crate::server::player::Player;
+use crate::server::utils::{constant_time_eq, log_error, parse_duration};
+use crate::server::Server;
+
+///everything in here requires an `Authorization: Bearer <admin_api_token>` header
+pub fn router(server: Arc<Server>, token: &str) -> Router {
+	Router::new()
+		.route("/players", get(list_players))
+		.route("/players/{id}/kick", post(kick))
+		.route("/players/{id}/teleport", post(teleport))
+		.route("/players/{id}/ban", post(ban))
+		.route("/players/{id}/mute", post(mute).delete(unmute))
+		.route("/bans", get(list_bans))
+		.route("/bans/{target}", delete(unban))
+		.route("/announce", post(announce))
+		.route("/command", post(command))
+		.route_layer(middleware::from_fn_with_state(Arc::<str>::from(token), authorize))
+		.with_state(server)
+}
+
+async fn authorize(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+	let authorized = request
+		.headers()
+		.get(AUTHORIZATION)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.strip_prefix("Bearer "))
+		.is_some_and(|provided| constant_time_eq(provided.as_bytes(), token.as_bytes()));
+
+	if !authorized {
+		return ApiError(StatusCode::UNAUTHORIZED, "missing or wrong token").into_response();
+	}
+
+	next.run(request).await
+}
+
+struct ApiError(StatusCode, &'static str);
+
+impl IntoResponse for ApiError {
+	fn into_response(self) -> Response {
+		(self.0, Json(json!({ "error": self.1 }))).into_response()
+	}
+}
+
+const PLAYER_NOT_FOUND: ApiError = ApiError(StatusCode::NOT_FOUND, "player not found");
+const INVALID_DURATION: ApiError = ApiError(StatusCode::BAD_REQUEST, "invalid duration");
+///credited for whatever is done through here
+const ISSUER: &str = "admin api";
+
+#[derive(Serialize)]
+struct PlayerInfo {
+	id: i64,
+	name: String,
+	ip: String,
+	class: String,
+	specialization: String,
+	level: i32,
+	position: [i64; 3],
+	team: Option<i32>
+}
+
+async fn list_players(State(server): State<Arc<Server>>) -> Json<Vec<PlayerInfo>> {
+	let players = server.players.read().await.clone();
+
+	players
+		.iter()
+		.map(async |player| {
+			let character = player.character.read().await;
+
+			PlayerInfo {
+				id: player.id.0,
+				name: character.name.clone(),
+				ip: player.address.ip().to_string(),
+				class: format!("{:?}", character.occupation),
+				specialization: format!("{:?}", character.specialization),
+				level: character.level,
+				position: character.position.into(),
+				team: player.addon_data.read().await.team
+			}
+		})
+		.pipe(join_all)
+		.await
+		.pipe(Json)
+}
+
+async fn find_by_id(server: &Server, id: i64) -> Result<Arc<Player>, ApiError> {
+	server
+		.players
+		.read()
+		.await
+		.iter()
+		.find(|player| player.id.0 == id)
+		.cloned()
+		.ok_or(PLAYER_NOT_FOUND)
+}
+
+#[derive(Deserialize)]
+struct KickRequest {
+	#[serde(default)]
+	reason: String
+}
+
+async fn kick(State(server): State<Arc<Server>>, Path(id): Path<i64>, Json(request): Json<KickRequest>) -> Result<StatusCode, ApiError> {
+	let action = format!("kick {} ({})", describe(&server, id).await, request.reason);
+	let result = async {
+		let target = find_by_id(&server, id).await?;
+		server
+			.kick(&target, request.reason).await
+			.then_some(StatusCode::NO_CONTENT)
+			.ok_or(ApiError(StatusCode::CONFLICT, "player is getting kicked already"))
+	}.await;
+
+	audit(&server, action, result).await
+}
+
+#[derive(Deserialize)]
+struct BanRequest {
+	#[serde(default)]
+	reason: String,
+	duration: Option<String>, //e.g. `12h`, permanent if absent
+	#[serde(default)]
+	ip: bool //bans the player's address instead of their name
+}
+
+async fn ban(State(server): State<Arc<Server>>, Path(id): Path<i64>, Json(request): Json<BanRequest>) -> Result<StatusCode, ApiError> {
+	let action = format!("ban {}{} ({})", describe(&server, id).await, if request.ip { " by address" } else { "" }, request.reason);
+	let result = async {
+		let duration = request.duration
+			.map(|duration| parse_duration(&duration).ok_or(INVALID_DURATION))
+			.transpose()?;
+
+		let player = find_by_id(&server, id).await?;
+		let target = if request.ip {
+			Target::Address(player.address.ip().into())
+		} else {
+			Target::name(&player.character.read().await.name)
+		};
+
+		server
+			.ban(Ban::new(target, request.reason, ISSUER.to_owned(), duration))
+			.await
+			.map_err(save_failed)?;
+		Ok(StatusCode::NO_CONTENT)
+	}.await;
+
+	audit(&server, action, result).await
+}
+
+#[derive(Deserialize)]
+struct MuteRequest {
+	#[serde(default)]
+	reason: String,
+	duration: Option<String> //e.g. `30m`, permanent if absent
+}
+
+async fn mute(State(server): State<Arc<Server>>, Path(id): Path<i64>, Json(request): Json<MuteRequest>) -> Result<StatusCode, ApiError> {
+	let duration_text = request.duration.as_ref().map_or("permanently".to_owned(), |duration| format!("for {duration}"));
+	let action = format!("mute {} {duration_text} ({})", describe(&server, id).await, request.reason);
+	let result = async {
+		let duration = request.duration
+			.map(|duration| parse_duration(&duration).ok_or(INVALID_DURATION))
+			.transpose()?;
+
+		let name = find_by_id(&server, id).await?.character.read().await.name.clone();
+		server.mute(&name, request.reason, duration, ISSUER).await;
+		Ok(StatusCode::NO_CONTENT)
+	}.await;
+
+	audit(&server, action, result).await
+}
+
+async fn unmute(State(server): State<Arc<Server>>, Path(id): Path<i64>) -> Result<StatusCode, ApiError> {
+	let action = format!("unmute {}", describe(&server, id).await);
+	let result = async {
+		let name = find_by_id(&server, id).await?.character.read().await.name.clone();
+
+		server
+			.unmute(&name, ISSUER).await
+			.then_some(StatusCode::NO_CONTENT)
+			.ok_or(ApiError(StatusCode::NOT_FOUND, "player isn't muted"))
+	}.await;
+
+	audit(&server, action, result).await
+}
+
+async fn list_bans(State(server): State<Arc<Server>>) -> Json<Vec<Ban>> {
+	server.addons.bans.list().await.pipe(Json)
+}
+
+///`target` is a name, an address or an address range, the latter with its slash percent-encoded
+async fn unban(State(server): State<Arc<Server>>, Path(target): Path<String>) -> Result<StatusCode, ApiError> {
+	let target = Target::parse_address(&target).unwrap_or_else(|| Target::name(&target));
+
+	let result = server
+		.unban(&target, ISSUER)
+		.await
+		.map_err(save_failed)
+		.and_then(|unbanned| unbanned.map(|_ban| StatusCode::NO_CONTENT).ok_or(ApiError(StatusCode::NOT_FOUND, "no such ban")));
+
+	audit(&server, format!("unban {target}"), result).await
+}
+
+///records the action along with how it went, then passes the result on.
+///commands sent through `/command` get recorded by the command manager instead
+async fn audit(server: &Server, action: String, result: Result<StatusCode, ApiError>) -> Result<StatusCode, ApiError> {
+	let outcome = match &result {
+		Ok(_) => "ok".to_owned(),
+		Err(ApiError(_, error)) => format!("failed ({error})")
+	};
+	server.audit(ISSUER.to_owned(), action, outcome).await;
+	result
+}
+
+///for the audit log, which should show who the action was aimed at even if they can't be found
+async fn describe(server: &Server, id: i64) -> String {
+	match find_by_id(server, id).await {
+		Ok(player) => format!("{} (player #{id})", player.character.read().await.name),
+		Err(_) => format!("player #{id}")
+	}
+}
+
+fn save_failed(error: std::io::Error) -> ApiError {
+	log_error("saving bans", error);
+	ApiError(StatusCode::INTERNAL_SERVER_ERROR, "failed to save bans")
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TeleportRequest {
+	ToPlayer { player: i64 },
+	ToPosition { position: [i64; 3] }
+}
+
+async fn teleport(State(server): State<Arc<Server>>, Path(id): Path<i64>, Json(request): Json<TeleportRequest>) -> Result<StatusCode, ApiError> {
+	let action = match &request {
+		TeleportRequest::ToPlayer { player } => format!("teleport {} to {}", describe(&server, id).await, describe(&server, *player).await),
+		TeleportRequest::ToPosition { position } => format!("teleport {} to {position:?}", describe(&server, id).await)
+	};
+	let result = async {
+		let target = find_by_id(&server, id).await?;
+		let destination = match request {
+			TeleportRequest::ToPlayer { player } => find_by_id(&server, player).await?.character.read().await.position,
+			TeleportRequest::ToPosition { position } => Point3::from(position)
+		};
+
+		server.teleport(&target, destination).await;
+		Ok(StatusCode::NO_CONTENT)
+	}.await;
+
+	audit(&server, action, result).await
+}
+
+#[derive(Deserialize)]
+struct AnnounceRequest {
+	text: String
+}
+
+async fn announce(State(server): State<Arc<Server>>, Json(request): Json<AnnounceRequest>) -> Result<StatusCode, ApiError> {
+	let action = format!("announce {}", request.text);
+	server.announce(request.text).await;
+
+	audit(&server, action, Ok(StatusCode::NO_CONTENT)).await
+}
+
+#[derive(Deserialize)]
+struct CommandRequest {
+	command: String //the leading slash is optional
+}
+
+async fn command(State(server): State<Arc<Server>>, Json(request): Json<CommandRequest>) -> Response {
+	let text = format!("/{}", request.command.trim_start_matches('/'));
+	let mut result = None;
+
+	server.addons.command_manager.on_message(
+		&server,
+		Issuer::AdminApi,
+		&text,
+		'/',
+		async |command_result| result = Some(command_result)
+	).await;
+
+	match result.expect("text always starts with the prefix") {
+		Ok(response) => Json(json!({ "response": response })).into_response(),
+		Err(error) => ApiError(StatusCode::BAD_REQUEST, error).into_response()
+	}
+}
@@ -1,53 +1,45 @@
 use std::ops::Div as _;
 use std::collections::HashMap;
+use std::io;
 use std::path;
 
-use config::{Config, ConfigError};
+use tokio::sync::RwLock;
+
 use protocol::utils::constants::{SIZE_BLOCK, SIZE_ZONE};
 use protocol::rgb::RGB8;
 use protocol::packet::world_update::Block;
 use protocol::nalgebra::{Point2, Vector3};
 use protocol::packet::world_update::block::Kind::*;
-use tap::Pipe;
+
+use crate::server::utils::log_error;
 
 mod vox;
 mod zox;
 
 pub struct Models {
-	models: Vec<(Point2<i32>, Vec<Block>)>
+	models: RwLock<Vec<(Point2<i32>, Vec<Block>)>>
 }
 
 impl Models {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
+	pub fn new(models: &HashMap<String, [i64; 3]>) -> Self {
 		Self {
-			models: config
-				.get::<HashMap<String, [i64; 3]>>("models")?
-				.into_iter()
-				.map(|(filename, pos)| {
-					let pos: Vector3<i64> = pos.into();
-					let zone = pos
-						.xy()
-						.div(SIZE_ZONE)
-						.cast::<i32>()
-						.into();
-
-					let mut blocks = parse_model(&filename);
-					let model_origin = pos
-						.div(SIZE_BLOCK)
-						.cast::<i32>();
-
-					for block in &mut blocks {
-						block.position += model_origin;
-					}
+			models: RwLock::new(place(models).expect("models are parsed by ServerConfig::validate already"))
+		}
+	}
 
-					(zone, blocks)
-				})
-				.collect()
-		}.pipe(Ok)
+	///clients only request each zone once, so changes become visible after reconnecting or moving far enough away.
+	///keeps the current models if one of them can't be parsed anymore (it could be parsed during validation)
+	pub async fn reload(&self, models: &HashMap<String, [i64; 3]>) {
+		match place(models) {
+			Ok(placed) => *self.models.write().await = placed,
+			Err(error) => log_error("reload-models", error)
+		}
 	}
 
-	pub fn blocks_in(&self, requested_zone: Point2<i32>) -> Vec<Block> {
+	pub async fn blocks_in(&self, requested_zone: Point2<i32>) -> Vec<Block> {
 		self.models
+			.read()
+			.await
 			.iter()
 			.filter(|(zone, _blocks)| *zone == requested_zone)
 			.flat_map(|(_zone, blocks)| blocks)
@@ -56,15 +48,43 @@ impl Models {
 	}
 }
 
+fn place(models: &HashMap<String, [i64; 3]>) -> io::Result<Vec<(Point2<i32>, Vec<Block>)>> {
+	models
+		.iter()
+		.map(|(filename, pos)| {
+			let pos: Vector3<i64> = (*pos).into();
+			let zone = pos
+				.xy()
+				.div(SIZE_ZONE)
+				.cast::<i32>()
+				.into();
+
+			let mut blocks = parse_model(filename)?;
+			let model_origin = pos
+				.div(SIZE_BLOCK)
+				.cast::<i32>();
+
+			for block in &mut blocks {
+				block.position += model_origin;
+			}
+
+			Ok((zone, blocks))
+		})
+		.collect()
+}
+
 const PURE_BLUE: RGB8 = RGB8::new(0, 0, 255);
 
-pub fn parse_model(filename: &str) -> Vec<Block> {
+///anything but .vox is taken to be .zox
+pub fn parse_model(filename: &str) -> io::Result<Vec<Block>> {
 	let path = path::Path::new(filename);
-	if path.extension().unwrap() == "vox" {
-		vox::parse(filename)
+	let voxels = if path.extension().is_some_and(|extension| extension == "vox") {
+		vox::parse(filename)?
 	} else {
-		zox::parse(path)
-	}
+		zox::parse(path)?
+	};
+
+	let blocks = voxels
 		.into_iter()
 		.map(|(position, color)| Block {
 			position,
@@ -72,5 +92,6 @@ pub fn parse_model(filename: &str) -> Vec<Block> {
 			kind: if color == PURE_BLUE { Liquid } else { Solid },
 			padding: 0,
 		})
-		.collect()
+		.collect();
+	Ok(blocks)
 }
\ No newline at end of file
@@ -1,16 +1,17 @@
 use std::collections::HashMap;
+use std::io::{self, ErrorKind::InvalidData};
 
 use dot_vox::SceneNode;
 use protocol::rgb::{RGB8, RGBA8};
 use protocol::nalgebra::{Point3, Vector3};
 use tap::{Conv, Pipe};
 
-pub fn parse(filename: &str) -> Vec<(Point3<i32>, RGB8)> {
-    let vox = dot_vox::load(filename).expect("vox load failed");
+pub fn parse(filename: &str) -> io::Result<Vec<(Point3<i32>, RGB8)>> {
+    let vox = dot_vox::load(filename).map_err(|error| io::Error::new(InvalidData, error))?;
 
 	let mut model_offsets = HashMap::new();
 	if !vox.scenes.is_empty() {
-		walk_scene_graph(&vox.scenes, 0, Vector3::zeros(), &mut model_offsets);
+		walk_scene_graph(&vox.scenes, 0, Vector3::zeros(), &mut model_offsets)?;
 	}
 
 	vox
@@ -34,38 +35,43 @@ pub fn parse(filename: &str) -> Vec<(Point3<i32>, RGB8)> {
                             .unwrap_or_else(Vector3::zeros);
                     
                     let color = palette_ref
-                        [voxel.i as usize]
+                        .get(voxel.i as usize)
+                        .ok_or_else(|| io::Error::new(InvalidData, "voxel color out of palette"))?
                         .conv::<[u8; 4]>()
                         .conv::<RGBA8>()
                         .rgb();
                     
-                    (pos, color)
+                    Ok((pos, color))
                 })
 		})
 		.collect()
 }
 
-fn walk_scene_graph(scene_graph: &Vec<SceneNode>, index: usize, mut current_offset: Vector3<i32>, model_offsets: &mut HashMap<u32, Vector3<i32>>) {
-    match &scene_graph[index] {
+fn walk_scene_graph(scene_graph: &Vec<SceneNode>, index: usize, mut current_offset: Vector3<i32>, model_offsets: &mut HashMap<u32, Vector3<i32>>) -> io::Result<()> {
+    let invalid = || io::Error::new(InvalidData, "malformed scene graph");
+
+    match scene_graph.get(index).ok_or_else(invalid)? {
 		#[expect(clippy::or_fun_call, reason = "false positive")]
         SceneNode::Transform { frames, child, .. } => {
-            current_offset += frames[0]
+            current_offset += frames
+                .first()
+                .ok_or_else(invalid)?
                 .attributes
                 .get("_t")
                 .unwrap_or(&"0 0 0".into())
                 .split(' ')
 				.map(str::parse)
-				.map(Result::unwrap)
-                .collect::<Vec<i32>>()
+                .collect::<Result<Vec<i32>, _>>()
+                .map_err(|_| invalid())?
                 .pipe(<[i32; 3]>::try_from)
-                .unwrap()
+                .map_err(|_| invalid())?
                 .conv::<Vector3::<i32>>();
 
-            walk_scene_graph(scene_graph, *child as usize, current_offset, model_offsets);
+            walk_scene_graph(scene_graph, *child as usize, current_offset, model_offsets)?;
         },
         SceneNode::Group { children, .. } => {
             for child in children {
-                walk_scene_graph(scene_graph, *child as usize, current_offset, model_offsets);
+                walk_scene_graph(scene_graph, *child as usize, current_offset, model_offsets)?;
             }
         },
         SceneNode::Shape { models, .. } => {
@@ -74,4 +80,6 @@ fn walk_scene_graph(scene_graph: &Vec<SceneNode>, index: usize, mut current_offs
             }
         },
     }
+
+    Ok(())
 }
\ No newline at end of file
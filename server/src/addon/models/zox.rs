@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{self, BufReader};
 use std::path::Path;
 
 use protocol::nalgebra::{Point3, Point4};
@@ -8,13 +8,11 @@ use serde::Deserialize;
 use tap::{Conv, Pipe};
 
 #[expect(clippy::big_endian_bytes, reason ="file format")]
-pub fn parse(path: &Path) -> Vec<(Point3<i32>, RGB8)> {
-    let zox = File::open(path)
-        .unwrap()
+pub fn parse(path: &Path) -> io::Result<Vec<(Point3<i32>, RGB8)>> {
+    let zox = File::open(path)?
         .pipe(BufReader::new)
-        .pipe(serde_json::from_reader::<_, Zox>)
-        .unwrap();
-    zox
+        .pipe(serde_json::from_reader::<_, Zox>)?;
+    let blocks = zox
         .frame1
         .into_iter()
         .map(Point4::from)
@@ -27,7 +25,9 @@ pub fn parse(path: &Path) -> Vec<(Point3<i32>, RGB8)> {
     
             (pos, color)
         })
-        .collect()
+        .collect();
+
+    Ok(blocks)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
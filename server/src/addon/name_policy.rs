@@ -0,0 +1,126 @@
+use std::ptr;
+use std::sync::Arc;
+
+use config::ConfigError;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::server::config::invalid;
+use crate::server::player::Player;
+use crate::server::Server;
+
+///cubeworld doesn't allow more, see [crate::addon::anti_cheat]
+const MAX_NAME_LENGTH: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Duplicates {
+	///turns the player away
+	Reject,
+	///appends a number, e.g. `bob2`
+	Suffix
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamesConfig {
+	pub duplicates: Duplicates,
+	pub reserved: Vec<String>,
+	pub blocked_words: Vec<String>
+}
+
+impl Default for NamesConfig {
+	fn default() -> Self {
+		Self {
+			duplicates: Duplicates::Suffix,
+			reserved: ["server", "console", "admin"].map(Into::into).into(),
+			blocked_words: vec![]
+		}
+	}
+}
+
+impl NamesConfig {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		for (key, entries) in [("names.reserved", &self.reserved), ("names.blocked_words", &self.blocked_words)] {
+			if entries.iter().any(|entry| entry.trim().is_empty()) {
+				return invalid(format!("{key} must not contain empty entries"));
+			}
+		}
+		Ok(())
+	}
+}
+
+///decides which character names are acceptable, both when joining and when changing names later on
+pub struct NamePolicy {
+	config: RwLock<NamesConfig>
+}
+
+impl NamePolicy {
+	pub fn new(config: NamesConfig) -> Self {
+		Self {
+			config: RwLock::new(config)
+		}
+	}
+
+	pub async fn reload(&self, config: NamesConfig) {
+		*self.config.write().await = config;
+	}
+
+	///returns the name the player gets to use, which differs from the requested one if it had to be suffixed.
+	///on refusal returns a message explaining why.
+	///`player` is the one asking for the name, if they are online already
+	pub async fn check(&self, server: &Server, name: &str, player: Option<&Player>) -> Result<String, String> {
+		let taken = taken_names(&server.joined_and_queued().await, player).await;
+		self.check_against(name, &taken).await
+	}
+
+	///like [Self::check], but against the given lowercase names instead of everyone online or queued
+	pub async fn check_against(&self, name: &str, taken: &[String]) -> Result<String, String> {
+		let config = self.config.read().await;
+		let lowercase = name.to_lowercase();
+
+		if name.trim().is_empty() {
+			return Err("your name must not be empty".into());
+		}
+		if name.trim() != name {
+			return Err("your name must not start or end with spaces".into()); //would get around the checks below, e.g. " admin"
+		}
+		if config.reserved.iter().any(|reserved| reserved.to_lowercase() == lowercase) {
+			return Err(format!("the name {name} is reserved, please pick another one"));
+		}
+		if config.blocked_words.iter().any(|word| lowercase.contains(&word.to_lowercase())) {
+			return Err("your name contains a word that isn't allowed here, please pick another one".into());
+		}
+
+		if !taken.contains(&lowercase) {
+			return Ok(name.to_owned());
+		}
+
+		match config.duplicates {
+			Duplicates::Reject => Err(format!("someone named {name} is online already, please pick another name")),
+			Duplicates::Suffix => Ok((2..)
+				.map(|number| with_suffix(name, number))
+				.find(|candidate| !taken.contains(&candidate.to_lowercase()))
+				.expect("there are more numbers than players"))
+		}
+	}
+}
+
+///lowercase names of the given players, except `player_to_skip`
+pub async fn taken_names(players: &[Arc<Player>], player_to_skip: Option<&Player>) -> Vec<String> {
+	let mut names = vec![];
+	for player in players {
+		if player_to_skip.is_some_and(|to_skip| ptr::eq(to_skip, player.as_ref())) {
+			continue;
+		}
+		names.push(player.character.read().await.name.to_lowercase());
+	}
+	names
+}
+
+///cuts off the end of the name if necessary to stay within the length limit
+fn with_suffix(name: &str, number: usize) -> String {
+	let suffix = number.to_string();
+	let kept = name.chars().take(MAX_NAME_LENGTH.saturating_sub(suffix.len())).collect::<String>();
+	format!("{kept}{suffix}")
+}
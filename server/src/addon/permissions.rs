@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::net::IpAddr;
+
+use config::ConfigError;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::server::config::{invalid, ServerConfig};
+use crate::server::player::Player;
+use crate::server::utils::constant_time_eq;
+
+///exempts from anti-cheat kicks
+pub const ANTI_CHEAT_BYPASS: &str = "anti_cheat.bypass";
+///receives whatever gets posted to the discord admin channel
+pub const ADMIN_NOTIFICATIONS: &str = "notifications.admin";
+///never gets kicked for being afk
+pub const AFK_EXEMPT: &str = "afk.exempt";
+///joins right away, regardless of free slots
+pub const QUEUE_BYPASS: &str = "queue.bypass";
+
+///the permission required to use a command
+pub fn command_permission(literal: &str) -> String {
+	format!("command.{literal}")
+}
+
+///who is doing something, which determines what they are permitted to do
+#[derive(Debug, Clone, Copy)]
+pub enum Issuer<'a> {
+	Player(&'a Player),
+	///the server console. permitted to do anything, just like the 2 below
+	Console,
+	Rcon,
+	AdminApi,
+	///only the admin channel is permitted to do anything, the public one is limited to the default permissions
+	Discord { admin_channel: bool, user: &'a str }
+}
+
+impl<'a> Issuer<'a> {
+	pub const fn player(self) -> Option<&'a Player> {
+		match self {
+			Self::Player(player) => Some(player),
+			_ => None
+		}
+	}
+}
+
+impl Display for Issuer<'_> {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Player(player)                          => write!(formatter, "player #{}", player.id.0),
+			Self::Console                                 => write!(formatter, "console"),
+			Self::Rcon                                    => write!(formatter, "rcon"),
+			Self::AdminApi                                => write!(formatter, "admin api"),
+			Self::Discord { admin_channel: true , user }  => write!(formatter, "{user} on the discord admin channel"),
+			Self::Discord { admin_channel: false, user }  => write!(formatter, "{user} on discord")
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Role {
+	pub password: Option<String>, //for `/login`
+	pub addresses: Vec<IpAddr>, //players joining from these get the role automatically
+	pub permissions: Vec<String>
+}
+
+///validates everything permission related in the config
+pub fn validate(config: &ServerConfig) -> Result<(), ConfigError> {
+	let permissions = config.roles
+		.iter()
+		.flat_map(|(name, role)| role.permissions.iter().map(move |permission| (format!("roles.{name}.permissions"), permission)))
+		.chain(config.default_permissions.iter().map(|permission| ("default_permissions".to_owned(), permission)));
+	for (key, permission) in permissions {
+		if permission.is_empty() || permission.contains(char::is_whitespace) {
+			return invalid(format!("{key} contains an invalid permission '{permission}'"));
+		}
+	}
+
+	for (name, role) in &config.roles {
+		//commands (and thereby `/role`) are case insensitive
+		if name.is_empty() || name.contains(char::is_whitespace) || name.chars().any(char::is_uppercase) {
+			return invalid(format!("role name '{name}' must be lowercase without whitespace"));
+		}
+		if role.password.as_deref().is_some_and(str::is_empty) {
+			return invalid(format!("roles.{name}.password must not be empty"));
+		}
+	}
+
+	Ok(())
+}
+
+///roles grant permissions on top of the default ones everyone has.
+///`*` grants everything and a trailing `.*` everything below, e.g. `command.*`
+pub struct Permissions {
+	rules: RwLock<Rules>
+}
+
+struct Rules {
+	default: Vec<String>,
+	roles: BTreeMap<String, Role>
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	roles: BTreeSet<String>
+}
+
+impl Permissions {
+	pub fn new(config: &ServerConfig) -> Self {
+		Self {
+			rules: RwLock::new(Rules::from(config))
+		}
+	}
+
+	pub async fn reload(&self, config: &ServerConfig) {
+		*self.rules.write().await = Rules::from(config);
+	}
+
+	pub async fn allows(&self, issuer: Issuer<'_>, permission: &str) -> bool {
+		let roles = match issuer {
+			Issuer::Console | Issuer::Rcon | Issuer::AdminApi | Issuer::Discord { admin_channel: true, .. } => return true,
+			Issuer::Discord { admin_channel: false, .. } => BTreeSet::new(),
+			Issuer::Player(player) => player.addon_data.read().await.permissions.roles.clone()
+		};
+
+		let rules = self.rules.read().await;
+		rules.default
+			.iter()
+			.chain(roles.iter().filter_map(|role| rules.roles.get(role)).flat_map(|role| &role.permissions))
+			.any(|granted| covers(granted, permission))
+	}
+
+	///like [Self::allows] for a connection that hasn't joined yet, so only has the roles of its address
+	pub async fn allows_address(&self, ip: IpAddr, permission: &str) -> bool {
+		let rules = self.rules.read().await;
+		rules.default
+			.iter()
+			.chain(rules.roles.values().filter(|role| role.addresses.contains(&ip)).flat_map(|role| &role.permissions))
+			.any(|granted| covers(granted, permission))
+	}
+
+	///whether everyone has the permission anyway
+	pub async fn is_default(&self, permission: &str) -> bool {
+		self.rules
+			.read()
+			.await
+			.default
+			.iter()
+			.any(|granted| covers(granted, permission))
+	}
+
+	///grants every role the player's address is listed for
+	pub async fn assign_by_address(&self, player: &Player) {
+		let ip = player.address.ip();
+		let matching = self.rules
+			.read()
+			.await
+			.roles
+			.iter()
+			.filter(|(_name, role)| role.addresses.contains(&ip))
+			.map(|(name, _role)| name.clone())
+			.collect::<Vec<_>>();
+
+		player.addon_data.write().await.permissions.roles.extend(matching);
+	}
+
+	///grants every role with a matching password and returns their names
+	pub async fn login(&self, player: &Player, password: &str) -> Vec<String> {
+		let matching = self.rules
+			.read()
+			.await
+			.roles
+			.iter()
+			//passwords arrive lowercased like everything else in a command
+			.filter(|(_name, role)| role.password.as_ref().is_some_and(|expected| constant_time_eq(expected.to_lowercase().as_bytes(), password.as_bytes())))
+			.map(|(name, _role)| name.clone())
+			.collect::<Vec<_>>();
+
+		player.addon_data.write().await.permissions.roles.extend(matching.iter().cloned());
+		matching
+	}
+
+	pub async fn grant(&self, player: &Player, role: &str) -> Result<(), &'static str> {
+		if !self.rules.read().await.roles.contains_key(role) {
+			return Err("unknown role");
+		}
+
+		player.addon_data.write().await.permissions.roles.insert(role.to_owned())
+			.then_some(())
+			.ok_or("player already has that role")
+	}
+
+	pub async fn revoke(&self, player: &Player, role: &str) -> Result<(), &'static str> {
+		player.addon_data.write().await.permissions.roles.remove(role)
+			.then_some(())
+			.ok_or("player doesn't have that role")
+	}
+
+	///grants those of the given roles that are still configured
+	pub async fn restore(&self, player: &Player, roles: &BTreeSet<String>) {
+		let configured = &self.rules.read().await.roles;
+		let restored = roles.iter().filter(|role| configured.contains_key(*role)).cloned();
+
+		player.addon_data.write().await.permissions.roles.extend(restored);
+	}
+
+	///the configured roles in alphabetical order
+	pub async fn role_names(&self) -> Vec<String> {
+		self.rules.read().await.roles.keys().cloned().collect()
+	}
+
+	pub async fn roles_of(&self, player: &Player) -> Vec<String> {
+		player.addon_data.read().await.permissions.roles.iter().cloned().collect()
+	}
+}
+
+impl From<&ServerConfig> for Rules {
+	fn from(config: &ServerConfig) -> Self {
+		Self {
+			default: config.default_permissions.clone(),
+			roles: config.roles.clone()
+		}
+	}
+}
+
+fn covers(granted: &str, permission: &str) -> bool {
+	if granted == "*" || granted == permission {
+		return true;
+	}
+
+	granted
+		.strip_suffix(".*")
+		.and_then(|prefix| permission.strip_prefix(prefix))
+		.is_some_and(|rest| rest.starts_with('.'))
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::ErrorKind::NotFound;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io;
+
+use crate::addon::pvp;
+use crate::server::config::ServerConfig;
+use crate::server::creature::Creature;
+use crate::server::player::Player;
+use crate::server::utils::{log_error, unix_now, write_atomically};
+use crate::server::Server;
+
+const PROFILES_DIRECTORY: &str = "profiles";
+
+///everything worth remembering about a character across sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+	pub name: String,
+	pub first_seen: u64, //unix seconds
+	pub last_seen: u64, //unix seconds
+	pub play_time: u64, //seconds
+	pub addresses: BTreeSet<IpAddr>,
+	pub last_position: Option<[i64; 3]>,
+	pub progression: Vec<Progress>,
+	pub notes: Vec<Note>,
+	pub roles: BTreeSet<String>, //granted through `/role`, see [Server::load_profile]
+	pub trusted_addresses: BTreeSet<IpAddr>, //those the roles were granted to, unlike `addresses` which includes anyone who used the name
+	addons: BTreeMap<String, serde_json::Value>, //see [Self::get]
+	#[serde(skip)]
+	counted_until: Option<Instant>, //play time up to this point is already included
+	#[serde(skip)]
+	loaded: bool //profiles that failed to load don't get saved, so they don't overwrite whatever is on disk
+}
+
+///a class or level change, in the order they were noticed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+	pub time: u64, //unix seconds
+	pub class: String,
+	pub specialization: String,
+	pub level: i32
+}
+
+///left by moderators through `/note`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+	pub time: u64, //unix seconds
+	pub author: String,
+	pub text: String
+}
+
+impl Profile {
+	fn new(name: String) -> Self {
+		Self {
+			name,
+			first_seen: unix_now(),
+			loaded: true,
+			..Default::default()
+		}
+	}
+
+	///data attached by an addon, stored under a key of its choice.
+	///none if there is nothing stored yet or it doesn't fit `T` (anymore)
+	pub fn get<T: DeserializeOwned>(&self, addon: &str) -> Option<T> {
+		self.addons
+			.get(addon)
+			.and_then(|value| T::deserialize(value).ok())
+	}
+
+	///none removes whatever was stored
+	pub fn set<T: Serialize>(&mut self, addon: &str, data: Option<&T>) {
+		match data.map(serde_json::to_value) {
+			Some(Ok(value)) => { self.addons.insert(addon.to_owned(), value); },
+			Some(Err(error)) => log_error("profile-addon-data", error),
+			None => { self.addons.remove(addon); }
+		}
+	}
+
+	///takes note of the character's current state
+	fn update(&mut self, character: &Creature) {
+		self.last_seen = unix_now();
+		self.last_position = Some(character.position.into());
+		if let Some(counted_until) = self.counted_until.replace(Instant::now()) {
+			self.play_time += counted_until.elapsed().as_secs();
+		}
+
+		let progress = Progress {
+			time: self.last_seen,
+			class: format!("{:?}", character.occupation),
+			specialization: format!("{:?}", character.specialization),
+			level: character.level
+		};
+		let changed = self.progression.last().is_none_or(|last| (&last.class, &last.specialization, last.level) != (&progress.class, &progress.specialization, progress.level));
+		if changed {
+			self.progression.push(progress);
+		}
+	}
+}
+
+///a directory with 1 json file per character name
+pub struct Profiles {
+	directory: PathBuf
+}
+
+impl Profiles {
+	pub fn new(config: &ServerConfig) -> Self {
+		Self {
+			directory: config.data_dir.join(PROFILES_DIRECTORY)
+		}
+	}
+
+	///none if the name has never been seen before
+	pub async fn load(&self, name: &str) -> io::Result<Option<Profile>> {
+		let data = match fs::read(self.path_of(name)).await {
+			Ok(data) => data,
+			Err(error) if error.kind() == NotFound => return Ok(None),
+			Err(error) => return Err(error)
+		};
+
+		let mut profile = serde_json::from_slice::<Profile>(&data)?;
+		profile.loaded = true;
+		Ok(Some(profile))
+	}
+
+	pub async fn save(&self, profile: &Profile) -> io::Result<()> {
+		if !profile.loaded {
+			return Ok(());
+		}
+
+		fs::create_dir_all(&self.directory).await?;
+		write_atomically(&self.path_of(&profile.name), serde_json::to_vec_pretty(profile)?).await
+	}
+
+	///every profile on disk. unreadable ones are skipped
+	pub async fn all(&self) -> io::Result<Vec<Profile>> {
+		let mut entries = match fs::read_dir(&self.directory).await {
+			Ok(entries) => entries,
+			Err(error) if error.kind() == NotFound => return Ok(vec![]),
+			Err(error) => return Err(error)
+		};
+
+		let mut profiles = vec![];
+		while let Some(entry) = entries.next_entry().await? {
+			let Ok(data) = fs::read(entry.path()).await
+				else { continue };
+			if let Ok(profile) = serde_json::from_slice(&data) {
+				profiles.push(profile);
+			}
+		}
+		Ok(profiles)
+	}
+
+	///names are case insensitive and may contain anything, so everything but letters and digits gets escaped
+	fn path_of(&self, name: &str) -> PathBuf {
+		let mut filename = String::new();
+		for byte in name.to_lowercase().bytes() {
+			if byte.is_ascii_alphanumeric() {
+				filename.push(byte as char);
+			} else {
+				_ = write!(filename, "_{byte:02x}");
+			}
+		}
+		self.directory.join(format!("{filename}.json"))
+	}
+}
+
+impl Server {
+	///to be called once the player has joined.
+	///roles granted through `/role` only come back when joining from an address they were granted to, as anyone can pick any name
+	pub async fn load_profile(&self, player: &Player) {
+		let name = player.character.read().await.name.clone();
+		let mut profile = match self.addons.profiles.load(&name).await {
+			Ok(profile) => profile.unwrap_or_else(|| Profile::new(name)),
+			Err(error) => {
+				log_error("load-profile", error);
+				return;
+			}
+		};
+
+		if profile.trusted_addresses.contains(&player.address.ip()) {
+			self.addons.permissions.restore(player, &profile.roles).await;
+		}
+		profile.addresses.insert(player.address.ip());
+		profile.update(&*player.character.read().await);
+		let team = profile.get::<i32>(pvp::team::PROFILE_KEY);
+		*player.profile.write().await = profile;
+
+		if team.is_some() {
+			pvp::team::change_to(self, player, team).await;
+		}
+	}
+
+	///to be called before the player gets removed
+	pub async fn save_profile(&self, player: &Player) {
+		let mut profile = player.profile.write().await;
+		profile.update(&*player.character.read().await);
+		profile.set(pvp::team::PROFILE_KEY, player.addon_data.read().await.team.as_ref());
+
+		_ = self.addons.profiles
+			.save(&profile)
+			.await
+			.inspect_err(|error| log_error("save-profile", error));
+	}
+
+	///the profile of an online player is edited in place (and saved when they leave), that of an offline one on disk.
+	///returns false if the name has never been seen
+	pub async fn edit_profile(&self, name: &str, edit: impl FnOnce(&mut Profile)) -> io::Result<bool> {
+		if let Some(player) = self.find_player_by_name(name).await {
+			edit(&mut *player.profile.write().await);
+			return Ok(true);
+		}
+
+		let Some(mut profile) = self.addons.profiles.load(name).await?
+			else { return Ok(false) };
+		edit(&mut profile);
+		self.addons.profiles.save(&profile).await?;
+		Ok(true)
+	}
+
+	///the current profile of an online player, otherwise the one on disk
+	pub async fn profile_of(&self, name: &str) -> io::Result<Option<Profile>> {
+		match self.find_player_by_name(name).await {
+			Some(player) => {
+				let mut profile = player.profile.read().await.clone();
+				profile.update(&*player.character.read().await);
+				Ok(Some(profile))
+			}
+			None => self.addons.profiles.load(name).await
+		}
+	}
+}
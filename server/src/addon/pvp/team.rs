@@ -16,6 +16,9 @@ use super::map_head;
 
 pub mod display;
 
+///players rejoin the team they left in, see [Server::load_profile]
+pub const PROFILE_KEY: &str = "team";
+
 pub async fn change_to(server: &Server, player: &Player, new_team: Option<i32>) -> bool {
 	let mut addon_data = player.addon_data.write().await;
 	if addon_data.team == new_team {
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{self, AsyncReadExt, BufReader, Chain};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::sync::{broadcast, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{sleep, timeout_at, Instant};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::addon::permissions::Issuer;
+use crate::server::config::ServerConfig;
+use crate::server::connection_limit::ConnectionLimit;
+use crate::server::token_bucket::TokenBucket;
+use crate::server::utils::{constant_time_eq, log_error};
+use crate::server::Server;
+
+mod line;
+mod source;
+
+///slows down guessing the password
+const WRONG_PASSWORD_DELAY: Duration = Duration::from_secs(1);
+///failed logins an ip can pile up before further attempts get refused, regaining 1 per minute
+const FAILED_LOGIN_BURST: f64 = 3.0;
+///events a slow client may fall behind on before it starts missing some
+const EVENT_BACKLOG: usize = 256;
+
+type Reader = BufReader<Chain<Cursor<Vec<u8>>, OwnedReadHalf>>;
+
+///remote console. clients authenticate with a password, after which they can run commands with admin rights
+///and receive announcements and chat as they happen. both plain text lines and the source rcon protocol are understood
+pub struct Rcon {
+	port: Option<u16>, //none if rcon is disabled
+	password: Option<String>,
+	events: broadcast::Sender<Event>,
+	connection_limit: ConnectionLimit, //separate from the game's, with the same settings
+	login_timeout: Duration, //see [ServerConfig::handshake_timeout]
+	failed_logins: Mutex<HashMap<IpAddr, TokenBucket>>
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+	Chat { name: String, text: String },
+	Announcement(String)
+}
+
+impl Event {
+	fn to_line(&self) -> String {
+		match self {
+			Self::Chat { name, text } => format!("[chat] {name}: {text}"),
+			Self::Announcement(text)  => format!("[announcement] {text}")
+		}
+	}
+}
+
+impl Rcon {
+	pub fn new(config: &ServerConfig) -> Self {
+		Self {
+			port: config.rcon_port,
+			password: config.rcon_password.clone(),
+			events: broadcast::Sender::new(EVENT_BACKLOG),
+			connection_limit: ConnectionLimit::new(config),
+			login_timeout: Duration::from_secs(config.handshake_timeout),
+			failed_logins: Mutex::default()
+		}
+	}
+
+	///forwards `event` to every authenticated client
+	pub fn publish(&self, event: Event) {
+		_ = self.events.send(event); //fails if nobody is connected, which is fine
+	}
+
+	pub async fn run(&self, server: Arc<Server>) {
+		let (Some(port), Some(password)) = (self.port, self.password.clone())
+			else { return };
+
+		let listener = TcpListener::bind((Ipv6Addr::UNSPECIFIED, port))
+			.await
+			.expect("failed to bind RCON socket");
+
+		let events = self.events.clone();
+		tokio::spawn(async move {
+			loop {
+				let (stream, address) = match listener.accept().await {
+					Ok(connection) => connection,
+					Err(error) => {
+						log_error("rcon accept", error);
+						continue;
+					}
+				};
+
+				let rcon = &server.addons.rcon;
+				if let Err(reason) = rcon.connection_limit.try_open(address.ip()).await {
+					info!(%address, reason, "dropped rcon connection");
+					continue;
+				}
+
+				let session = Session {
+					server: Arc::clone(&server),
+					password: password.clone(),
+					events: events.clone(),
+					address,
+					login_deadline: Instant::now() + rcon.login_timeout
+				};
+				tokio::spawn(
+					async move {
+						let server = Arc::clone(&session.server);
+						if let Err(error) = session.run(stream).await {
+							info!("rcon connection lost: {error}");
+						}
+						server.addons.rcon.connection_limit.close(address.ip()).await;
+					}.instrument(info_span!("rcon", %address))
+				);
+			}
+		});
+	}
+}
+
+struct Session {
+	server: Arc<Server>,
+	password: String,
+	events: broadcast::Sender<Event>,
+	address: SocketAddr,
+	login_deadline: Instant //clients that stall before authenticating would otherwise occupy a connection indefinitely
+}
+
+impl Session {
+	async fn run(self, stream: TcpStream) -> io::Result<()> {
+		let address = self.address;
+		let (mut read, write) = stream.into_split();
+
+		//source rcon starts with a little endian packet length, which never exceeds 2 bytes. text has no zero bytes in it
+		let mut prefix = Vec::with_capacity(4);
+		while prefix.len() < 4 && prefix.last() != Some(&b'\n') {
+			prefix.push(timeout_at(self.login_deadline, read.read_u8()).await??);
+		}
+		let is_source = prefix.len() == 4 && prefix[2..] == [0, 0];
+		let reader = BufReader::new(Cursor::new(prefix).chain(read));
+
+		info!(%address, protocol = if is_source { "source" } else { "line" }, "rcon connection");
+		if is_source {
+			source::run(self, reader, write).await
+		} else {
+			line::run(self, reader, write).await
+		}
+	}
+
+	///refuses any attempt while the ip has too many failed ones
+	async fn authenticate(&self, attempt: &str) -> bool {
+		let ip = self.address.ip();
+		let mut failed_logins = self.server.addons.rcon.failed_logins.lock().await;
+		let failures = failed_logins
+			.entry(ip)
+			.or_insert_with(|| TokenBucket::new(FAILED_LOGIN_BURST, 1.0 / 60.0));
+
+		if failures.is_empty() {
+			drop(failed_logins);
+			warn!("rcon authentication refused due to too many failed attempts");
+			sleep(WRONG_PASSWORD_DELAY).await;
+			return false;
+		}
+
+		let correct = constant_time_eq(attempt.as_bytes(), self.password.as_bytes());
+		if correct {
+			info!("rcon authenticated");
+		} else {
+			_ = failures.try_take();
+		}
+
+		//forget addresses that have nothing left to remember
+		failed_logins.retain(|_ip, failures| !failures.is_full());
+		drop(failed_logins);
+
+		if !correct {
+			warn!("rcon authentication failed");
+			sleep(WRONG_PASSWORD_DELAY).await;
+		}
+		correct
+	}
+
+	async fn execute(&self, command: &str) -> Option<String> {
+		crate::console::execute(&self.server, Issuer::Rcon, command).await
+	}
+}
+
+///skips over events the client fell behind on, returns none once the server is gone
+async fn next_event(events: &mut broadcast::Receiver<Event>) -> Option<Event> {
+	loop {
+		match events.recv().await {
+			Ok(event) => return Some(event),
+			Err(RecvError::Lagged(missed)) => warn!(missed, "rcon client fell behind on events"),
+			Err(RecvError::Closed) => return None
+		}
+	}
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use tokio::io::{self, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::Mutex;
+use tokio::time::timeout_at;
+
+use crate::addon::rcon::{next_event, Reader, Session};
+
+const MAX_LINE_LENGTH: u64 = 4096;
+
+///the first line is the password. afterwards every line is a command, answered by exactly 1 line
+///(`ok` if the command has no output). events are interleaved as lines starting with `[chat]` or `[announcement]`
+pub(super) async fn run(session: Session, mut reader: Reader, mut writer: OwnedWriteHalf) -> io::Result<()> {
+	let Some(password) = timeout_at(session.login_deadline, read_line(&mut reader)).await??
+		else { return Ok(()) };
+
+	if !session.authenticate(&password).await {
+		return writer.write_all(b"wrong password\n").await;
+	}
+	writer.write_all(b"authenticated\n").await?;
+
+	let writer = Arc::new(Mutex::new(writer));
+	let mut events = session.events.subscribe();
+	let forwarding = tokio::spawn({
+		let writer = Arc::clone(&writer);
+		async move {
+			while let Some(event) = next_event(&mut events).await {
+				if writer.lock().await.write_all(format!("{}\n", event.to_line()).as_bytes()).await.is_err() {
+					break;
+				}
+			}
+		}
+	});
+
+	let result = async {
+		while let Some(command) = read_line(&mut reader).await? {
+			if command.is_empty() {
+				continue;
+			}
+			let response = session.execute(&command).await.unwrap_or_else(|| "ok".into());
+			writer.lock().await.write_all(format!("{}\n", response.replace('\n', " ")).as_bytes()).await?;
+		}
+		Ok(())
+	}.await;
+
+	forwarding.abort();
+	result
+}
+
+///returns none once the client disconnects
+async fn read_line(reader: &mut Reader) -> io::Result<Option<String>> {
+	let mut line = String::new();
+	let length = (&mut *reader).take(MAX_LINE_LENGTH).read_line(&mut line).await?;
+
+	if length == 0 {
+		return Ok(None);
+	}
+	if !line.ends_with('\n') && length as u64 == MAX_LINE_LENGTH {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "line too long"));
+	}
+
+	Ok(Some(line.trim_end_matches(['\r', '\n']).to_owned()))
+}
@@ -0,0 +1,125 @@
+//https://developer.valvesoftware.com/wiki/Source_RCON_Protocol
+
+use std::sync::Arc;
+
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::Mutex;
+use tokio::time::timeout_at;
+
+use crate::addon::rcon::{next_event, Reader, Session};
+
+const AUTH: i32 = 3;
+const AUTH_RESPONSE: i32 = 2;
+const EXEC_COMMAND: i32 = 2;
+const RESPONSE_VALUE: i32 = 0;
+
+///id + type + 2 terminators
+const MIN_PACKET_SIZE: i32 = 10;
+const MAX_PACKET_SIZE: i32 = 4096;
+const MAX_BODY_LENGTH: usize = (MAX_PACKET_SIZE - MIN_PACKET_SIZE) as usize;
+///events aren't a response to anything, so they get an id no client should be using
+const EVENT_ID: i32 = -1;
+
+struct Packet {
+	id: i32,
+	kind: i32,
+	body: String
+}
+
+pub(super) async fn run(session: Session, mut reader: Reader, mut writer: OwnedWriteHalf) -> io::Result<()> {
+	let Some(auth) = timeout_at(session.login_deadline, read_packet(&mut reader)).await??
+		else { return Ok(()) };
+
+	let authenticated = auth.kind == AUTH && session.authenticate(&auth.body).await;
+	write_packet(&mut writer, auth.id, RESPONSE_VALUE, "").await?; //source servers send this first, so some clients expect it
+	write_packet(&mut writer, if authenticated { auth.id } else { -1 }, AUTH_RESPONSE, "").await?;
+	if !authenticated {
+		return Ok(());
+	}
+
+	let writer = Arc::new(Mutex::new(writer));
+	let mut events = session.events.subscribe();
+	let forwarding = tokio::spawn({
+		let writer = Arc::clone(&writer);
+		async move {
+			while let Some(event) = next_event(&mut events).await {
+				if write_response(&mut *writer.lock().await, EVENT_ID, &event.to_line()).await.is_err() {
+					break;
+				}
+			}
+		}
+	});
+
+	let result = async {
+		while let Some(packet) = read_packet(&mut reader).await? {
+			match packet.kind {
+				EXEC_COMMAND => {
+					let response = session.execute(&packet.body).await.unwrap_or_default();
+					write_response(&mut *writer.lock().await, packet.id, &response).await?;
+				}
+				//mirrored, so clients can tell where a response split across multiple packets ends
+				RESPONSE_VALUE => write_packet(&mut *writer.lock().await, packet.id, RESPONSE_VALUE, "").await?,
+				_ => {}
+			}
+		}
+		Ok(())
+	}.await;
+
+	forwarding.abort();
+	result
+}
+
+///returns none once the client disconnects
+async fn read_packet(reader: &mut Reader) -> io::Result<Option<Packet>> {
+	let size = match reader.read_i32_le().await {
+		Ok(size) => size,
+		Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(error) => return Err(error)
+	};
+	if !(MIN_PACKET_SIZE..=MAX_PACKET_SIZE).contains(&size) {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, format!("invalid packet size {size}")));
+	}
+
+	let id = reader.read_i32_le().await?;
+	let kind = reader.read_i32_le().await?;
+	let mut body = vec![0; (size - 8) as usize];
+	reader.read_exact(&mut body).await?;
+	body.truncate(body.iter().position(|byte| *byte == 0).unwrap_or(body.len())); //cuts off the terminators
+
+	Ok(Some(Packet {
+		id,
+		kind,
+		body: String::from_utf8_lossy(&body).into_owned()
+	}))
+}
+
+async fn write_packet(writer: &mut OwnedWriteHalf, id: i32, kind: i32, body: &str) -> io::Result<()> {
+	let size = MIN_PACKET_SIZE + body.len() as i32;
+
+	let mut bytes = Vec::with_capacity(size as usize + 4);
+	bytes.extend(size.to_le_bytes());
+	bytes.extend(id.to_le_bytes());
+	bytes.extend(kind.to_le_bytes());
+	bytes.extend(body.as_bytes());
+	bytes.extend([0, 0]);
+
+	writer.write_all(&bytes).await
+}
+
+///splits `body` across as many packets as necessary
+async fn write_response(writer: &mut OwnedWriteHalf, id: i32, mut body: &str) -> io::Result<()> {
+	loop {
+		let mut end = body.len().min(MAX_BODY_LENGTH);
+		while !body.is_char_boundary(end) {
+			end -= 1;
+		}
+		let (chunk, rest) = body.split_at(end);
+		write_packet(writer, id, RESPONSE_VALUE, chunk).await?;
+
+		if rest.is_empty() {
+			return Ok(());
+		}
+		body = rest;
+	}
+}
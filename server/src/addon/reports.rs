@@ -0,0 +1,146 @@
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use tokio::io;
+use tokio::sync::RwLock;
+
+use protocol::packet::common::item::Kind;
+use protocol::packet::creature_update::equipment::Slot;
+
+use crate::server::config::ServerConfig;
+use crate::server::player::Player;
+use crate::server::utils::{format_duration, log_error, unix_now, write_atomically};
+use crate::server::Server;
+
+const REPORTS_FILE: &str = "reports.json";
+
+///a complaint about a player along with what they were up to at the time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+	pub id: usize,
+	pub time: u64, //unix seconds
+	pub reporter: String,
+	pub target: String,
+	pub reason: String,
+	pub position: [i64; 3],
+	pub class: String,
+	pub level: i32,
+	pub health: String,
+	pub equipment: Vec<String>,
+	pub near_misses: Vec<String> //see [crate::addon::anti_cheat::PlayerData::note_near_miss]
+}
+
+impl Display for Report {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		writeln!(formatter, "report #{} by {} against {}: {}", self.id, self.reporter, self.target, self.reason)?;
+		writeln!(formatter, "level {} {}, health {}, at {:?}", self.level, self.class, self.health, self.position)?;
+		writeln!(formatter, "equipment: {}", if self.equipment.is_empty() { "none".to_owned() } else { self.equipment.join(", ") })?;
+		write!(formatter, "anti-cheat near misses: {}", if self.near_misses.is_empty() { "none".to_owned() } else { self.near_misses.join(", ") })
+	}
+}
+
+///reports filed through `/report`, persisted in the data directory
+pub struct Reports {
+	path: PathBuf,
+	cooldown: Duration,
+	reports: RwLock<Vec<Report>>
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	last_report: Option<Instant>
+}
+
+impl Reports {
+	pub fn new(config: &ServerConfig) -> Self {
+		let path = config.data_dir.join(REPORTS_FILE);
+		let reports = match fs::read(&path) {
+			Ok(data) => serde_json::from_slice(&data).expect("failed to parse reports"), //see [crate::addon::bans::Bans::new]
+			Err(error) if error.kind() == NotFound => vec![],
+			Err(error) => panic!("failed to read reports: {error}")
+		};
+
+		Self {
+			path,
+			cooldown: Duration::from_secs(config.report_cooldown),
+			reports: RwLock::new(reports)
+		}
+	}
+
+	///the latest `amount` reports, oldest first
+	pub async fn latest(&self, amount: usize) -> Vec<Report> {
+		let reports = self.reports.read().await;
+		reports[reports.len().saturating_sub(amount)..].to_vec()
+	}
+
+	///assigns the id
+	async fn add(&self, mut report: Report) -> io::Result<Report> {
+		let mut reports = self.reports.write().await;
+		report.id = reports.last().map_or(1, |last| last.id + 1);
+		reports.push(report.clone());
+		write_atomically(&self.path, serde_json::to_vec_pretty(&*reports)?).await?;
+		Ok(report)
+	}
+}
+
+impl Server {
+	///snapshots the target, stores the report and forwards it to the admins.
+	///on refusal returns the message to show to the reporter
+	pub async fn report(&self, reporter: &Player, target: &Player, reason: String) -> Result<Report, String> {
+		let reports = &self.addons.reports;
+
+		let mut addon_data = reporter.addon_data.write().await;
+		let last_report = &mut addon_data.reports.last_report;
+		if let Some(remaining) = last_report.map(|last| reports.cooldown.saturating_sub(last.elapsed())) && !remaining.is_zero() {
+			return Err(format!("please wait {} before reporting again", format_duration(remaining)));
+		}
+		*last_report = Some(Instant::now());
+		drop(addon_data);
+
+		let report = snapshot(reporter, target, reason).await;
+		let report = reports.add(report).await.map_err(|error| {
+			log_error("saving reports", error);
+			"failed to save your report, please try again later".to_owned()
+		})?;
+
+		self.notify_admins(report.to_string()).await;
+		Ok(report)
+	}
+}
+
+async fn snapshot(reporter: &Player, target: &Player, reason: String) -> Report {
+	let character = target.character.read().await;
+	let near_misses = target.addon_data
+		.read()
+		.await
+		.anti_cheat_data
+		.near_misses()
+		.map(|(time, description)| format!("{description} ({} ago)", format_duration(time.elapsed())))
+		.collect();
+
+	Report {
+		id: 0,
+		time: unix_now(),
+		reporter: reporter.character.read().await.name.clone(),
+		target: character.name.clone(),
+		reason,
+		position: character.position.into(),
+		class: format!("{:?} ({:?})", character.occupation, character.specialization),
+		level: character.level,
+		#[expect(clippy::cast_possible_truncation, reason = "only for display")]
+		health: format!("{}/{}", character.health as i32, character.maximum_health() as i32),
+		equipment: Slot::iter()
+			.filter(|slot| character.equipment[*slot].kind != Kind::Void)
+			.map(|slot| {
+				let item = &character.equipment[slot];
+				format!("{slot:?}: {:?} (rarity {}, level {})", item.kind, item.rarity, item.level)
+			})
+			.collect(),
+		near_misses
+	}
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::Duration;
+
+use config::ConfigError;
+use serde::Deserialize;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::sleep;
+
+use crate::addon::bans::{Ban, Target};
+use crate::addon::permissions::{command_permission, Issuer};
+use crate::server::config::invalid;
+use crate::server::player::Player;
+use crate::server::utils::{format_duration, log_error};
+use crate::server::Server;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VotingConfig {
+	pub duration: u64, //seconds
+	pub quorum: f64, //fraction of the players online who have to vote yes
+	pub min_kick_voters: usize, //players online apart from the target
+	pub kick_ban_duration: u64 //seconds
+}
+
+impl Default for VotingConfig {
+	fn default() -> Self {
+		Self {
+			duration: 60,
+			quorum: 0.5,
+			min_kick_voters: 3,
+			kick_ban_duration: 600
+		}
+	}
+}
+
+impl VotingConfig {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.duration < 1 {
+			return invalid("voting.duration must be at least 1");
+		}
+		if !(0.0..=1.0).contains(&self.quorum) {
+			return invalid("voting.quorum must be within 0.0..=1.0");
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+	Kick { name: String, reason: String },
+	Poll(String)
+}
+
+impl Display for Subject {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Kick { name, reason } if reason.is_empty() => write!(formatter, "kicking {name}"),
+			Self::Kick { name, reason } => write!(formatter, "kicking {name} ({reason})"),
+			Self::Poll(question) => write!(formatter, "'{question}'")
+		}
+	}
+}
+
+struct Vote {
+	id: u64,
+	subject: Subject,
+	ballots: HashMap<String, bool> //lowercase names of the voters
+}
+
+///1 vote at a time, open to everyone online. it ends once everyone voted or the time is up
+pub struct Voting {
+	config: RwLock<VotingConfig>,
+	current: Mutex<Option<Vote>>,
+	next_id: AtomicU64
+}
+
+impl Voting {
+	pub fn new(config: VotingConfig) -> Self {
+		Self {
+			config: RwLock::new(config),
+			current: Mutex::default(),
+			next_id: AtomicU64::new(0)
+		}
+	}
+
+	pub async fn reload(&self, config: VotingConfig) {
+		*self.config.write().await = config;
+	}
+}
+
+impl Server {
+	///the starter's vote counts as yes
+	pub async fn start_vote(self: &Arc<Self>, starter: &Player, subject: Subject) -> Result<(), &'static str> {
+		if let Subject::Kick { name, .. } = &subject {
+			let target = self.find_player_by_name(name).await.ok_or("target not found")?;
+			if self.addons.permissions.allows(Issuer::Player(&target), &command_permission("kick")).await {
+				return Err("moderators can't be vote-kicked");
+			}
+		}
+
+		let voting = &self.addons.voting;
+		let config = voting.config.read().await.clone();
+		if let Subject::Kick { name, .. } = &subject && self.voters().await.iter().filter(|voter| **voter != name.to_lowercase()).count() < config.min_kick_voters {
+			return Err("not enough players online to vote-kick");
+		}
+		let duration = Duration::from_secs(config.duration);
+		let starter_name = starter.character.read().await.name.clone();
+
+		let mut current = voting.current.lock().await;
+		if current.is_some() {
+			return Err("another vote is in progress");
+		}
+
+		let id = voting.next_id.fetch_add(1, Relaxed);
+
+		self.announce(match &subject {
+			Subject::Kick { .. } => format!("{starter_name} wants to vote on {subject}. type /vote yes or /vote no within {}", format_duration(duration)),
+			Subject::Poll(question) => format!("{starter_name} asks: {question}. type /vote yes or /vote no within {}", format_duration(duration))
+		}).await;
+		*current = Some(Vote {
+			id,
+			subject,
+			ballots: HashMap::from([(starter_name.to_lowercase(), true)])
+		});
+		drop(current);
+
+		let server = Arc::clone(self);
+		tokio::spawn(async move {
+			sleep(duration).await;
+			server.finish_vote(id).await;
+		});
+
+		self.finish_vote_if_everyone_voted().await;
+		Ok(())
+	}
+
+	///voting again changes the previous vote
+	pub async fn cast_vote(&self, voter: &Player, yes: bool) -> Result<(), &'static str> {
+		let name = voter.character.read().await.name.to_lowercase();
+
+		let mut current = self.addons.voting.current.lock().await;
+		let vote = current.as_mut().ok_or("there is no vote in progress")?;
+		if is_target(vote, &name) {
+			return Err("you can't vote on your own kick");
+		}
+		vote.ballots.insert(name, yes);
+		drop(current);
+
+		self.finish_vote_if_everyone_voted().await;
+		Ok(())
+	}
+
+	async fn finish_vote_if_everyone_voted(&self) {
+		let voters = self.voters().await;
+		let id = match self.addons.voting.current.lock().await.as_ref() {
+			Some(vote) if voters.iter().all(|voter| vote.ballots.contains_key(voter) || is_target(vote, voter)) => vote.id,
+			_ => return
+		};
+		self.finish_vote(id).await;
+	}
+
+	///does nothing if the vote has ended already
+	async fn finish_vote(&self, id: u64) {
+		let mut current = self.addons.voting.current.lock().await;
+		if current.as_ref().is_none_or(|vote| vote.id != id) {
+			return;
+		}
+		let vote = current.take().expect("checked above");
+		drop(current);
+
+		let config = self.addons.voting.config.read().await.clone();
+		let voters = self.voters().await;
+		let eligible = voters.iter().filter(|voter| !is_target(&vote, voter)).count();
+		//only ballots of those still online count
+		let (yes, no) = voters.iter().filter_map(|voter| vote.ballots.get(voter)).fold((0, 0), |(yes, no), ballot| if *ballot { (yes + 1, no) } else { (yes, no + 1) });
+		#[expect(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "player counts are small and positive")]
+		let required = ((eligible as f64 * config.quorum).ceil() as usize).max(1);
+		//players leaving during the vote may leave too few to kick
+		let passed = yes >= required && yes > no && (matches!(vote.subject, Subject::Poll(_)) || eligible >= config.min_kick_voters);
+
+		let verdict = if passed { "passed" } else { "failed" };
+		self.announce(format!("the vote on {} {verdict} ({yes} yes, {no} no, {required} needed)", vote.subject)).await;
+
+		if passed && let Subject::Kick { name, reason } = vote.subject {
+			let reason = if reason.is_empty() { "vote-kick".to_owned() } else { format!("vote-kick: {reason}") };
+			let ban = Ban::new(Target::name(&name), reason, "vote".to_owned(), Some(Duration::from_secs(config.kick_ban_duration)));
+			_ = self.ban(ban).await.inspect_err(|error| log_error("saving bans", error));
+		}
+	}
+
+	///lowercase names of everyone online
+	async fn voters(&self) -> Vec<String> {
+		let players = self.players.read().await.clone();
+
+		let mut names = vec![];
+		for player in players {
+			names.push(player.character.read().await.name.to_lowercase());
+		}
+		names
+	}
+}
+
+fn is_target(vote: &Vote, voter: &str) -> bool {
+	matches!(&vote.subject, Subject::Kick { name, .. } if name.to_lowercase() == voter)
+}
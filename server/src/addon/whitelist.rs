@@ -0,0 +1,106 @@
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::io;
+use tokio::sync::RwLock;
+
+use crate::addon::bans::Target;
+use crate::server::config::ServerConfig;
+use crate::server::utils::write_atomically;
+
+const WHITELIST_FILE: &str = "whitelist.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct State {
+	enabled: bool,
+	entries: Vec<Target>
+}
+
+///while enabled, only listed names and addresses may join. persisted in the data directory, including whether it is enabled
+pub struct Whitelist {
+	path: PathBuf,
+	message: RwLock<String>,
+	state: RwLock<State>
+}
+
+impl Whitelist {
+	pub fn new(config: &ServerConfig) -> Self {
+		let path = config.data_dir.join(WHITELIST_FILE);
+		let state = match fs::read(&path) {
+			Ok(data) => serde_json::from_slice(&data).expect("failed to parse whitelist"), //see [crate::addon::bans::Bans::new]
+			Err(error) if error.kind() == NotFound => State::default(),
+			Err(error) => panic!("failed to read whitelist: {error}")
+		};
+
+		Self {
+			path,
+			message: RwLock::new(config.whitelist_message.clone()),
+			state: RwLock::new(state)
+		}
+	}
+
+	pub async fn reload(&self, config: &ServerConfig) {
+		config.whitelist_message.clone_into(&mut *self.message.write().await);
+	}
+
+	///on refusal returns the message to show
+	pub async fn admits(&self, name: &str, address: IpAddr) -> Result<(), String> {
+		let state = self.state.read().await;
+		if !state.enabled || state.entries.iter().any(|entry| entry.matches(name, address)) {
+			return Ok(());
+		}
+
+		Err(self.message.read().await.clone())
+	}
+
+	pub async fn is_enabled(&self) -> bool {
+		self.state.read().await.enabled
+	}
+
+	///returns false if it already was in the requested state
+	pub async fn set_enabled(&self, enabled: bool) -> io::Result<bool> {
+		let mut state = self.state.write().await;
+		if state.enabled == enabled {
+			return Ok(false);
+		}
+
+		state.enabled = enabled;
+		self.save(&state).await?;
+		Ok(true)
+	}
+
+	///returns false if it was listed already
+	pub async fn add(&self, entry: Target) -> io::Result<bool> {
+		let mut state = self.state.write().await;
+		if state.entries.contains(&entry) {
+			return Ok(false);
+		}
+
+		state.entries.push(entry);
+		self.save(&state).await?;
+		Ok(true)
+	}
+
+	///returns false if it wasn't listed
+	pub async fn remove(&self, entry: &Target) -> io::Result<bool> {
+		let mut state = self.state.write().await;
+		let Some(index) = state.entries.iter().position(|listed| listed == entry)
+			else { return Ok(false) };
+
+		state.entries.remove(index);
+		self.save(&state).await?;
+		Ok(true)
+	}
+
+	pub async fn entries(&self) -> Vec<Target> {
+		self.state.read().await.entries.clone()
+	}
+
+	async fn save(&self, state: &State) -> io::Result<()> {
+		write_atomically(&self.path, serde_json::to_vec_pretty(state)?).await
+	}
+}
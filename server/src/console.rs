@@ -0,0 +1,184 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rustyline::{DefaultEditor, ExternalPrinter};
+use rustyline::error::ReadlineError;
+use tokio::runtime::Handle;
+
+use crate::addon::permissions::Issuer;
+use crate::server::shutdown::{Outcome, SIGNAL_DELAY};
+use crate::server::utils::log_error;
+use crate::server::Server;
+
+const PROMPT: &str = "> ";
+const HISTORY_FILE: &str = "console_history";
+
+///the server's stdin. every line is run as a command with admin rights, except for `say <text>` which announces `text`
+pub struct Console {
+	editor: DefaultEditor,
+	output: Option<ConsoleOutput>,
+	history: PathBuf
+}
+
+impl Console {
+	pub fn open(data_dir: &Path) -> rustyline::Result<Self> {
+		let mut editor = DefaultEditor::new()?;
+		let history = data_dir.join(HISTORY_FILE);
+		_ = editor.load_history(&history); //doesn't exist yet on first launch
+
+		//fails if stdin/stdout isn't a terminal, in which case there is no prompt to print around anyway
+		let output = editor
+			.create_external_printer()
+			.ok()
+			.map(|printer| ConsoleOutput(Box::new(printer)));
+
+		Ok(Self { editor, output, history })
+	}
+
+	///a writer that prints above the prompt instead of through it. only available once, and only on terminals
+	pub const fn take_output(&mut self) -> Option<ConsoleOutput> {
+		self.output.take()
+	}
+
+	///reading lines blocks, so this happens on a dedicated thread. the returned guard restores the terminal when dropped
+	pub fn spawn(self, server: Arc<Server>) -> TerminalGuard {
+		let guard = TerminalGuard::capture();
+		let runtime = Handle::current();
+
+		thread::Builder::new()
+			.name("console".into())
+			.spawn(move || self.run(&runtime, &server))
+			.expect("failed to spawn console thread");
+
+		guard
+	}
+
+	fn run(mut self, runtime: &Handle, server: &Arc<Server>) {
+		let mut interrupted = false;
+
+		loop {
+			let line = match self.editor.readline(PROMPT) {
+				Ok(line) => line,
+				Err(ReadlineError::Interrupted) => {
+					//raw mode swallows SIGINT, so ctrl+c needs to be handled here. a second one skips the countdown
+					let delay = if interrupted { Duration::ZERO } else { SIGNAL_DELAY };
+					interrupted = true;
+					runtime.block_on(server.schedule_shutdown(delay, None, Outcome::Exit));
+					continue;
+				}
+				Err(ReadlineError::Eof) => return, //stdin got closed or redirected from /dev/null
+				Err(error) => {
+					log_error("console", error);
+					return;
+				}
+			};
+
+			if line.trim().is_empty() {
+				continue;
+			}
+
+			_ = self.editor.add_history_entry(&line);
+			if let Err(error) = self.editor.save_history(&self.history) {
+				log_error("failed to save console history", error);
+			}
+
+			if let Some(response) = runtime.block_on(execute(server, Issuer::Console, &line)) {
+				println!("{response}");
+			}
+		}
+	}
+}
+
+///runs a line typed into the console (or sent through rcon, see `issuer`), returning what to print in response
+pub async fn execute(server: &Arc<Server>, issuer: Issuer<'_>, line: &str) -> Option<String> {
+	let line = line.trim();
+
+	if let Some(text) = line.strip_prefix("say").filter(|rest| rest.is_empty() || rest.starts_with(' ')) {
+		let text = text.trim();
+		if text.is_empty() {
+			return Some("usage: say <text>".into());
+		}
+		server.announce(text).await;
+		return None;
+	}
+
+	let mut response = None;
+	server.addons.command_manager.on_message(
+		server,
+		issuer,
+		&format!("/{}", line.trim_start_matches('/')), //the slash is optional
+		'/',
+		async |command_result| {
+			response = match command_result {
+				Ok(response) => response,
+				Err(error) => Some(error.into())
+			};
+		}
+	).await;
+
+	response
+}
+
+///see [Console::take_output]
+pub struct ConsoleOutput(Box<dyn ExternalPrinter + Send>);
+
+impl Write for ConsoleOutput {
+	fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+		self.0
+			.print(String::from_utf8_lossy(buffer).into_owned())
+			.map_err(io::Error::other)?;
+
+		Ok(buffer.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+///the console thread is still waiting for input in raw mode when the server stops,
+///so the terminal needs to be reset manually before exiting or restarting
+pub struct TerminalGuard {
+	#[cfg(unix)]
+	original: Option<libc::termios>
+}
+
+impl TerminalGuard {
+	#[cfg(unix)]
+	fn capture() -> Self {
+		if !io::stdin().is_terminal() {
+			return Self { original: None };
+		}
+
+		let mut original = std::mem::MaybeUninit::uninit();
+		//SAFETY: stdin stays open for the lifetime of the process and the pointer refers to a termios sized buffer
+		let return_code = unsafe { libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) };
+		if return_code != 0 {
+			return Self { original: None };
+		}
+
+		Self {
+			//SAFETY: tcgetattr succeeded, which means it filled in the struct
+			original: Some(unsafe { original.assume_init() })
+		}
+	}
+
+	#[cfg(not(unix))]
+	const fn capture() -> Self {
+		Self {}
+	}
+}
+
+impl Drop for TerminalGuard {
+	fn drop(&mut self) {
+		#[cfg(unix)]
+		if let Some(original) = &self.original {
+			//SAFETY: `original` was filled in by tcgetattr for the same file descriptor. there's nothing left to do if this fails
+			unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original) };
+			println!(); //move past the abandoned prompt
+		}
+	}
+}
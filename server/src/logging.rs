@@ -0,0 +1,237 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use config::ConfigError;
+use serde::Deserialize;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{self, RollingFileAppender};
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::console::ConsoleOutput;
+use crate::server::config::invalid;
+
+const FILE_PREFIX: &str = "berld";
+const FILE_SUFFIX: &str = "log";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+	pub level: String,
+	pub console: ConsoleFormat,
+	pub files: bool,
+	pub rotation: Rotation,
+	pub max_files: usize //0 keeps everything
+}
+
+impl Default for LogConfig {
+	fn default() -> Self {
+		Self {
+			level: "info".into(),
+			console: ConsoleFormat::Pretty,
+			files: true,
+			rotation: Rotation::Daily,
+			max_files: 14
+		}
+	}
+}
+
+impl LogConfig {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if let Err(error) = EnvFilter::try_new(&self.level) {
+			return invalid(format!("log.level is invalid: {error}"));
+		}
+		if self.rotation == Rotation::Size(0) {
+			return invalid("log.rotation size must be at least 1 byte");
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleFormat {
+	Pretty,
+	Json
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Rotation {
+	Never,
+	Minutely,
+	Hourly,
+	Daily,
+	Size(u64) //bytes
+}
+
+impl TryFrom<String> for Rotation {
+	type Error = String;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		let rotation = match value.as_str() {
+			"never"    => Self::Never,
+			"minutely" => Self::Minutely,
+			"hourly"   => Self::Hourly,
+			"daily"    => Self::Daily,
+			size => {
+				let unrecognized = || format!("'{value}' is neither never/minutely/hourly/daily nor a size like 10MB");
+				let unit_start = size.find(|character: char| !character.is_ascii_digit()).unwrap_or(size.len());
+				let (amount, unit) = size.split_at(unit_start);
+				let factor = match unit.trim() {
+					"KB" => 1 << 10,
+					"MB" => 1 << 20,
+					"GB" => 1 << 30,
+					_ => return Err(unrecognized())
+				};
+				let amount = amount
+					.parse::<u64>()
+					.map_err(|_| format!("'{value}' lacks a number"))?;
+
+				Self::Size(amount.checked_mul(factor).ok_or_else(unrecognized)?)
+			}
+		};
+
+		Ok(rotation)
+	}
+}
+
+///installs the global subscriber. console output goes to stdout unless `console_output` is given.
+///both that and file output are written on background threads,
+///so the returned guards need to be kept alive until exiting to not lose any events
+pub fn init(config: &LogConfig, data_dir: &Path, console_output: Option<ConsoleOutput>) -> io::Result<Vec<WorkerGuard>> {
+	let (console_writer, console_guard) = match console_output {
+		Some(output) => {
+			let (writer, guard) = tracing_appender::non_blocking(output);
+			(BoxMakeWriter::new(writer), Some(guard))
+		}
+		None => (BoxMakeWriter::new(io::stdout), None)
+	};
+	let console = match config.console {
+		ConsoleFormat::Pretty => fmt::layer().with_writer(console_writer).boxed(),
+		ConsoleFormat::Json   => fmt::layer().json().with_writer(console_writer).boxed()
+	};
+
+	let (files, file_guard) = if config.files {
+		let directory = data_dir.join("logs");
+		let writer: Box<dyn Write + Send> = match config.rotation {
+			Rotation::Size(limit) => Box::new(SizeRotatingFile::open(directory, limit, config.max_files)?),
+			Rotation::Never       => Box::new(time_rotating_file(directory, rolling::Rotation::NEVER   , config.max_files)?),
+			Rotation::Minutely    => Box::new(time_rotating_file(directory, rolling::Rotation::MINUTELY, config.max_files)?),
+			Rotation::Hourly      => Box::new(time_rotating_file(directory, rolling::Rotation::HOURLY  , config.max_files)?),
+			Rotation::Daily       => Box::new(time_rotating_file(directory, rolling::Rotation::DAILY   , config.max_files)?)
+		};
+		let (writer, guard) = tracing_appender::non_blocking(writer);
+
+		(Some(fmt::layer().json().with_writer(writer)), Some(guard))
+	} else {
+		(None, None)
+	};
+
+	tracing_subscriber::registry()
+		.with(EnvFilter::new(&config.level))
+		.with(console)
+		.with(files)
+		.init();
+
+	Ok([console_guard, file_guard].into_iter().flatten().collect())
+}
+
+fn time_rotating_file(directory: PathBuf, rotation: rolling::Rotation, max_files: usize) -> io::Result<RollingFileAppender> {
+	fs::create_dir_all(&directory)?; //the appender would complain about it missing when looking for old files to prune
+
+	let mut builder = RollingFileAppender::builder()
+		.rotation(rotation)
+		.filename_prefix(FILE_PREFIX)
+		.filename_suffix(FILE_SUFFIX);
+
+	if max_files > 0 {
+		builder = builder.max_log_files(max_files);
+	}
+
+	builder
+		.build(directory)
+		.map_err(io::Error::other)
+}
+
+///appends to `berld.log`, which gets renamed to `berld.log.<unix millis>` once it would exceed `limit` bytes
+struct SizeRotatingFile {
+	directory: PathBuf,
+	limit: u64,
+	max_files: usize,
+	file: File,
+	size: u64
+}
+
+impl SizeRotatingFile {
+	fn open(directory: PathBuf, limit: u64, max_files: usize) -> io::Result<Self> {
+		fs::create_dir_all(&directory)?;
+		let file = open_append(&directory)?;
+		let size = file.metadata()?.len();
+
+		Ok(Self { directory, limit, max_files, file, size })
+	}
+
+	fn rotate(&mut self) -> io::Result<()> {
+		let millis = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.expect("time went backwards")
+			.as_millis();
+		let current = self.directory.join(format!("{FILE_PREFIX}.{FILE_SUFFIX}"));
+
+		fs::rename(&current, self.directory.join(format!("{FILE_PREFIX}.{FILE_SUFFIX}.{millis}")))?;
+		self.file = open_append(&self.directory)?;
+		self.size = 0;
+
+		if self.max_files > 0 {
+			self.remove_oldest()?;
+		}
+
+		Ok(())
+	}
+
+	fn remove_oldest(&self) -> io::Result<()> {
+		let rotated_prefix = format!("{FILE_PREFIX}.{FILE_SUFFIX}.");
+		let mut rotated = fs::read_dir(&self.directory)?
+			.filter_map(Result::ok)
+			.map(|entry| entry.path())
+			.filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(&rotated_prefix)))
+			.collect::<Vec<_>>();
+		rotated.sort(); //the millis have a constant amount of digits for the next 260 years
+
+		//the current file counts towards the limit as well
+		let excess = (rotated.len() + 1).saturating_sub(self.max_files);
+		for path in &rotated[..excess.min(rotated.len())] {
+			fs::remove_file(path)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Write for SizeRotatingFile {
+	fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+		if self.size > 0 && self.size + buffer.len() as u64 > self.limit {
+			self.rotate()?;
+		}
+
+		let written = self.file.write(buffer)?;
+		self.size += written as u64;
+		Ok(written)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.file.flush()
+	}
+}
+
+fn open_append(directory: &Path) -> io::Result<File> {
+	OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(directory.join(format!("{FILE_PREFIX}.{FILE_SUFFIX}")))
+}
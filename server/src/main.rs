@@ -4,33 +4,129 @@
 #![feature(iter_collect_into)]
 #![feature(iter_intersperse)]
 #![feature(bool_to_result)]
+#![feature(min_specialization)]
 
 #![allow(unreachable_pub, reason = "this isn't a lib, so adding `(crate)` to every `pub` is just pointless noise")]
 #![allow(clippy::partial_pub_fields, reason = "OOP...")]
 
-use std::sync::LazyLock;
+use std::env;
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::process::{exit, Command};
+use std::sync::Arc;
 
-use colour::magenta_ln;
-use config::{Config, File, Environment};
+use clap::Parser;
+use colour::{magenta_ln, red_ln};
+use config::{Config, ConfigError, File, Environment};
+use console::Console;
+use server::config::{EXAMPLE, ServerConfig};
+use server::shutdown::Outcome;
 use server::Server;
-use tap::Pipe;
 
 mod server;
 mod addon;
+mod logging;
+mod console;
+#[cfg(test)]
+mod tests;
 
-static SERVER: LazyLock<Server> = LazyLock::new(||
-	Config::builder()
-		.add_source(File::with_name("config"))
-		.add_source(Environment::with_prefix("BERLD"))
-		.build()
-		.unwrap()
-		.pipe_ref(Server::new)
-		.unwrap()
-);
+///yet another CubeWorld alpha server.
+///arguments take precedence over environment variables, which take precedence over the config file
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Arguments {
+	///path to the config file, the extension may be omitted
+	#[arg(short, long, default_value = "config")]
+	config: String,
+	///address to accept game connections on. can be repeated to listen on multiple addresses
+	#[arg(short, long)]
+	bind: Vec<Ipv4Addr>,
+	///port to accept game connections on
+	#[arg(short, long)]
+	port: Option<u16>,
+	///directory for logs and persistent data
+	#[arg(short, long)]
+	data_dir: Option<PathBuf>,
+	///print a complete example config file and exit
+	#[arg(long)]
+	print_default_config: bool
+}
+
+impl Arguments {
+	fn load_config(&self) -> Result<ServerConfig, ConfigError> {
+		let bind = (!self.bind.is_empty()).then(|| self.bind.iter().map(ToString::to_string).collect::<Vec<_>>());
+		let data_dir = self.data_dir.as_ref().map(|path| path.to_string_lossy().into_owned());
+
+		Config::builder()
+			.add_source(File::with_name(&self.config))
+			.add_source(
+				Environment::with_prefix("BERLD")
+					.try_parsing(true)
+					.list_separator(",")
+					.with_list_parse_key("bind")
+			)
+			.set_override_option("bind", bind)?
+			.set_override_option("port", self.port)?
+			.set_override_option("data_dir", data_dir)?
+			.build()
+			.and_then(ServerConfig::load)
+	}
+}
 
 #[tokio::main]
 async fn main() {
+	let arguments = Arguments::parse();
+	if arguments.print_default_config {
+		print!("{EXAMPLE}");
+		return;
+	}
+
 	magenta_ln!("===== Berld =====");
 
-	SERVER.run().await;
-}
\ No newline at end of file
+	let config = arguments
+		.load_config()
+		.unwrap_or_else(|error| {
+			red_ln!("invalid config: {error}");
+			exit(1);
+		});
+
+	fs::create_dir_all(&config.data_dir).expect("failed to create data directory");
+	let mut console = Console::open(&config.data_dir).expect("failed to open console");
+	let log_guards = logging::init(&config.log, &config.data_dir, console.take_output()).expect("failed to open log file");
+
+	let server = Arc::new(Server::new(&config, Box::new(move || arguments.load_config())));
+	let terminal_guard = console.spawn(Arc::clone(&server));
+	tokio::spawn(Arc::clone(&server).handle_signals());
+	#[cfg(unix)]
+	tokio::spawn(Arc::clone(&server).handle_reload_signals());
+
+	let outcome = server.run(&config.bind, config.port).await;
+	drop(log_guards); //flushes pending events, which restarting would skip
+	drop(terminal_guard);
+	if outcome == Outcome::Restart {
+		restart();
+	}
+}
+
+///replaces the current process with a fresh instance using the same arguments
+#[cfg(unix)]
+fn restart() -> ! {
+	use std::os::unix::process::CommandExt as _;
+
+	let error = env::current_exe()
+		.map(Command::new)
+		.map(|mut command| command.args(env::args_os().skip(1)).exec());
+
+	panic!("failed to restart: {error:?}");
+}
+
+///spawns a fresh instance using the same arguments, as there is no way to replace the current process
+#[cfg(not(unix))]
+fn restart() -> ! {
+	env::current_exe()
+		.and_then(|executable| Command::new(executable).args(env::args_os().skip(1)).spawn())
+		.expect("failed to restart");
+
+	exit(0);
+}
@@ -2,52 +2,69 @@ use std::collections::HashMap;
 use std::net::Ipv4Addr;
 use std::io::ErrorKind::{InvalidData, InvalidInput, UnexpectedEof};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::ptr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use colour::dark_grey_ln;
-use config::{Config, ConfigError};
 use futures::future::join_all;
 use tap::{Pipe, Tap};
 use tokio::task::JoinHandle;
 use tokio::{io, select};
-use tokio::io::{copy, simplex, AsyncWrite, AsyncWriteExt, SimplexStream, WriteHalf};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::io::BufReader;
 use tokio::net::{TcpListener, TcpStream};
 use tokio::net::tcp::OwnedReadHalf;
-use tokio::sync::RwLock;
-use tokio::time::{sleep, timeout};
+use tokio::sync::{watch, Mutex, RwLock};
+use tokio::time::{sleep, timeout, timeout_at, Instant};
+use tracing::{debug, field, info, info_span, Instrument as _, Span};
 
 use protocol::{Packet, WriteCwData};
 use protocol::nalgebra::{Point2, Point3, Vector3};
-use protocol::packet::{*, Hit};
+use protocol::packet::{self, *, Hit};
 use protocol::packet::area_request::{Region, Zone};
 use protocol::packet::common::{CreatureId, Item};
 use protocol::packet::creature_update::Affiliation;
 use protocol::packet::world_update::loot::GroundItem;
 use protocol::packet::world_update::Sound;
 use protocol::packet::world_update::sound::Kind::*;
+use protocol::utils::compression::{CompressionStatistics, MeasuredBuffer};
 use protocol::utils::constants::{SIZE_BLOCK, SIZE_ZONE};
 use protocol::utils::io_extensions::{ReadPacket, WriteArbitrary, WritePacket};
 
 use crate::addon::{Addons, announce_join_leave};
 use crate::addon::pvp::map_head;
 use crate::addon::pvp;
+use crate::server::config::{ConfigLoader, ServerConfig};
+use crate::server::connection_limit::ConnectionLimit;
 use crate::server::creature::Creature;
 use crate::server::creature_id_pool::CreatureIdPool;
 use crate::server::handle_packet::HandlePacket;
+use crate::server::join_queue::JoinQueue;
+use crate::server::metrics::Metrics;
+use crate::server::outbound::Outbound;
 use crate::server::player::Player;
-use crate::SERVER;
+use crate::server::shutdown::Outcome;
 
 use self::utils::log_error;
 
+pub mod config;
+pub mod connection_limit;
 pub mod creature_id_pool;
+mod join_queue;
+pub mod metrics;
+pub mod outbound;
+mod persistence;
+mod reload;
+pub mod shutdown;
+pub mod token_bucket;
 pub mod player;
 mod handle_packet;
 pub mod creature;
 pub mod utils;
 
+///the port cubeworld clients connect to by default
+pub const PORT: u16 = 12345;
 const TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct Server {
@@ -55,37 +72,77 @@ pub struct Server {
 	pub players: RwLock<Vec<Arc<Player>>>,
 	loot: RwLock<HashMap<Point2<i32>, Vec<GroundItem>>>,
 	pub mapseed: i32,
-	pub motd: String,
-	pub addons: Addons
+	pub motd: RwLock<String>,
+	pub data_dir: PathBuf,
+	pub addons: Addons,
+	pub metrics: Metrics,
+	join_queue: JoinQueue,
+	connection_limit: ConnectionLimit,
+	handshake_timeout: Duration,
+	load_config: ConfigLoader,
+	shutdown_countdown: Mutex<Option<JoinHandle<()>>>,
+	stop: watch::Sender<Option<Outcome>>
 }
 
 impl Server {
-	pub fn new(config: &Config) -> Result<Self, ConfigError> {
-		let instance = Self {
+	///`load_config` is used for reloading and should yield `config` when called now
+	pub fn new(config: &ServerConfig, load_config: ConfigLoader) -> Self {
+		Self {
 			id_pool: Default::default(),
 			players: Default::default(),
 			loot: Default::default(),
-			mapseed: config.get("seed")?,
-			motd: config.get("motd")?,
-			addons: Addons::new(config)?,
-		};
+			mapseed: config.seed,
+			motd: RwLock::new(config.motd.clone()),
+			data_dir: config.data_dir.clone(),
+			addons: Addons::new(config),
+			metrics: Metrics::default(),
+			join_queue: JoinQueue::new(config),
+			connection_limit: ConnectionLimit::new(config),
+			handshake_timeout: Duration::from_secs(config.handshake_timeout),
+			load_config,
+			shutdown_countdown: Mutex::default(),
+			stop: watch::Sender::new(None)
+		}
+	}
+
+	pub async fn run(self: Arc<Self>, addresses: &[Ipv4Addr], port: u16) -> Outcome {
+		let mut listeners = vec![];
+		for address in addresses {
+			TcpListener
+				::bind((*address, port))
+				.await
+				.unwrap_or_else(|error| panic!("unable to bind listening socket on {address}:{port}: {error}"))
+				.pipe(|listener| listeners.push(listener));
+		}
 
-		Ok(instance)
+		self.serve(listeners).await
 	}
 
-	pub async fn run(&self) -> ! {
+	pub async fn serve(self: Arc<Self>, listeners: Vec<TcpListener>) -> Outcome {
 		self.initialize_id_pool().await;
-		self.addons.start().await;
+		self.load_state().await.expect("failed to load saved state");
+		self.addons.start(&self).await;
+
+		let accept_loops = listeners
+			.into_iter()
+			.map(|listener| {
+				let server = Arc::clone(&self);
+				async move {
+					loop { // infinite
+						_ = server.spawn_session(&listener).await;
+					}
+				}
+			})
+			.pipe(join_all);
 
-		//cubeworld is incapable of ipv6 networking
-		let listener = TcpListener
-			::bind((Ipv4Addr::UNSPECIFIED, 12345))
-			.await
-			.expect("unable to bind listening socket");
+		//dropping the accept loops closes the listeners
+		let outcome = select! {
+			_ = accept_loops => unreachable!("accept loops never end"),
+			outcome = self.wait_for_stop() => outcome
+		};
 
-		loop { // infinite
-			_ = self.spawn_session(&listener).await;
-		}
+		self.shut_down(outcome).await;
+		outcome
 	}
 
 	async fn initialize_id_pool(&self) {
@@ -94,49 +151,81 @@ impl Server {
 		pvp::team::display::reserve_dummy_ids(&mut id_pool);
 	}
 	
-	async fn spawn_session(&self, listener: &TcpListener) -> io::Result<()> {
+	async fn spawn_session(self: &Arc<Self>, listener: &TcpListener) -> io::Result<()> {
 		let (stream, address) = listener
 			.accept()
 			.await
 			.inspect_err(|err| log_error("tcp-accept", err))
 			?;
 
-		dark_grey_ln!("new connection from {}", address);
+		//dropping the stream right away keeps floods from tying up any further resources
+		if let Err(reason) = self.connection_limit.try_open(address.ip()).await {
+			info!(%address, reason, "dropped connection");
+			return Ok(());
+		}
+
+		debug!(%address, "new connection");
 
+		//id and name are filled in once the handshake completes
+		let span = info_span!("session", %address, id = field::Empty, name = field::Empty);
+		let server = Arc::clone(self);
 		tokio::spawn(async move {
-			_ = SERVER
+			_ = server
 				.initialize_session(stream, address)
 				.await
 				.inspect_err(|err| log_error("handle-new-connection", err));
-		});
+
+			server.connection_limit.close(address.ip()).await;
+		}.instrument(span));
 		
 		Ok(())
 	}
 
-	async fn initialize_session(&self, stream: TcpStream, address: SocketAddr) -> io::Result<()> {
-		let (mut reader, mut writer, join_handle) = configure_stream(stream)?;
+	async fn initialize_session(self: &Arc<Self>, stream: TcpStream, address: SocketAddr) -> io::Result<()> {
+		let (mut reader, mut writer, join_handle) = configure_stream(stream, self.metrics.compression())?;
 
-		match check_version(&mut reader, &mut writer).await {
-			Ok(())                                      => writer.write_packet(&ConnectionAcceptance).await?,
-			Err(error) if error.kind() == UnexpectedEof => return Ok(()), // prevent listforge's preiodic connections from flooding the logs,
-			Err(error)                                  => return Err(error)
-		}
+		let handshake = self.handshake(&mut reader, &mut writer, address).await;
+		let Ok(Some((assigned_id, character))) = handshake
+			else {
+				_ = writer.shutdown().await; //lets the copy task flush what's left and close the connection
+				return handshake.map(|_| ());
+			};
 
-		let assigned_id = self.assign_id(&mut writer).await?;
-		let (initial_creature_update, character) = read_character_data(&mut reader).await?;
+		Span::current()
+			.record("id", assigned_id.0)
+			.record("name", character.name.as_str());
 
 		let (player, kick_receiver) = Player::new(
 			address,
 			assigned_id,
 			character,
 			writer,
+			self.metrics.packets_sent()
 		);
 		let player = Arc::new(player);
 
-		self.players.write().await.push(Arc::clone(&player));
+		self.addons.permissions.assign_by_address(&player).await; //might grant [crate::addon::permissions::QUEUE_BYPASS]
+		match self.take_slot(&player, &mut reader).await {
+			Ok(true) => {}
+			Ok(false) => {
+				_ = player.writer.write().await.shutdown().await; //lets the copy task flush the refusal and close the connection
+				self.id_pool.write().await.free(assigned_id);
+				return Ok(());
+			}
+			Err(error) => {
+				join_handle.abort();
+				self.id_pool.write().await.free(assigned_id);
+				return Err(error);
+			}
+		}
+
+		info!("joined");
 
-		self.handle_packet(&player, initial_creature_update).await;
+		//the character might have changed while waiting in the queue
+		let current_state = player.character.read().await.to_update(player.id);
+		self.handle_packet(&player, current_state).await;
 		self.initialize_player(&player).await;
+		self.load_profile(&player).await;
 		
 		select! {
 			biased;
@@ -144,12 +233,71 @@ impl Server {
 			() = self.read_packets_forever(&player, reader) => {}
 		};
 		join_handle.abort();
+		info!("left");
+		self.save_profile(&player).await;
 		self.remove_player(&player).await;
 		self.id_pool.write().await.free(assigned_id);
 
 		Ok(())
 	}
 	
+	///returns none if the connection got turned down
+	async fn handshake(&self, reader: &mut BufReader<OwnedReadHalf>, writer: &mut Outbound, address: SocketAddr) -> io::Result<Option<(CreatureId, Creature)>> {
+		//clients that stall the handshake would otherwise occupy a task (and possibly an id) indefinitely
+		let deadline = Instant::now() + self.handshake_timeout;
+
+		match timeout_at(deadline, check_version(reader, writer)).await {
+			Ok(Ok(())) if self.is_full(address).await       => { writer.write_packet(&ConnectionRejection).await?; return Ok(None) },
+			Ok(Ok(()))                                      => writer.write_packet(&ConnectionAcceptance).await?,
+			Ok(Err(error)) if error.kind() == UnexpectedEof => return Ok(None), // prevent listforge's preiodic connections from flooding the logs,
+			Ok(Err(error))                                  => return Err(error),
+			Err(_elapsed)                                   => return Ok(None) // same goes for stalled connections
+		}
+
+		let assigned_id = self.assign_id(writer).await?;
+		let result = match timeout_at(deadline, read_character_data(reader)).await {
+			Ok(Ok(mut character)) => match self.admit(&character.name, address).await {
+				Ok(name) => {
+					if name != character.name {
+						writer.write_packet(&ChatMessageFromServer {
+							source: CreatureId(0),
+							text: format!("someone named {} is online already, so you are {name} here", character.name)
+						}).await?;
+						character.name = name;
+					}
+					return Ok(Some((assigned_id, character)));
+				}
+				Err(message) => writer.write_packet(&ChatMessageFromServer {
+					source: CreatureId(0),
+					text: message
+				}).await.map(|()| None)
+			},
+			Ok(Err(error)) => Err(error),
+			Err(_elapsed) => Ok(None)
+		};
+
+		self.id_pool.write().await.free(assigned_id);
+		result
+	}
+
+	///checks bans, the whitelist and the name policy. returns the name to use (see [crate::addon::name_policy::NamePolicy::check]) or a message explaining the refusal
+	async fn admit(&self, name: &str, address: SocketAddr) -> Result<String, String> {
+		if let Some(ban) = self.addons.bans.find(name, address.ip()).await {
+			info!(name, target = %ban.target, "rejected banned player");
+			return Err(ban.message());
+		}
+
+		self.addons.whitelist
+			.admits(name, address.ip())
+			.await
+			.inspect_err(|_message| info!(name, "rejected player not on the whitelist"))?;
+
+		self.addons.name_policy
+			.check(self, name, None)
+			.await
+			.inspect_err(|reason| info!(name, reason, "rejected name"))
+	}
+
 	async fn assign_id<Writable: AsyncWrite + Unpin + Send>(&self, writable: &mut Writable) -> io::Result<CreatureId> {
 		let assigned_id = self
 			.id_pool
@@ -186,7 +334,7 @@ impl Server {
 	async fn send_motd(&self, player: &Player) {
 		let message = format!(
 			"{}\n{} player(s) online",
-			self.motd,
+			self.motd.read().await,
 			self.players.read().await.len()
 		);
 		
@@ -194,15 +342,17 @@ impl Server {
 	}
 
 	pub async fn broadcast<Packet: FromServer>(&self, packet: &Packet, player_to_skip: Option<&Player>)
-		where Vec<u8>: WriteCwData<Packet>//todo: specialization could obsolete this
+		where for<'statistics> MeasuredBuffer<'statistics>: WriteCwData<Packet>//todo: specialization could obsolete this
 	{
-		let mut data = vec![];
+		let start = Instant::now();
+		let compression = self.metrics.compression();
+		let mut buffer = MeasuredBuffer::new(&compression);
 		
-		data.write_packet(packet).await.expect("failed to serialize a packet in-memory");
+		buffer.write_packet(packet).await.expect("failed to serialize a packet in-memory");
+		let data = buffer.data;
 		
-		_ = self.players
-			.read()
-			.await
+		let players = self.players.read().await;
+		let delivered = players
 			.iter()
 			.filter(|player| !player_to_skip.is_some_and(|pts| ptr::eq(player.as_ref(), pts)))
 			.map(async |player| {
@@ -215,10 +365,17 @@ impl Server {
 				writer.flush().await
 			})
 			.pipe(join_all)
-			.await;
+			.await
+			.into_iter()
+			.filter(Result::is_ok)
+			.count();
+		drop(players);
+
+		self.metrics.count_sent(&Packet::ID, delivered as u64);
+		self.metrics.observe_broadcast(start.elapsed());
 	}
 
-	pub async fn add_drop(&self, item: Item, position: Point3<i64>, rotation: f32) {
+	pub async fn add_drop(self: &Arc<Self>, item: Item, position: Point3<i64>, rotation: f32) {
 		let zone = position.xy().map(|scalar| (scalar / SIZE_ZONE) as i32);
 
 		let mut loot = self.loot.write().await;
@@ -242,9 +399,10 @@ impl Server {
 			..Default::default()
 		}, None).await;
 
+		let server = Arc::clone(self);
 		tokio::spawn(async move {
 			sleep(Duration::from_millis(500)).await;
-			SERVER.broadcast(&WorldUpdate::from(Sound::at(position, DropItem)), None).await;
+			server.broadcast(&WorldUpdate::from(Sound::at(position, DropItem)), None).await;
 		});
 	}
 
@@ -280,24 +438,26 @@ impl Server {
 			.expect("this should be the only place where players get removed");
 		let player = players.swap_remove(index);
 		drop(players);
+		self.join_queue.on_player_removed();
 		announce_join_leave(self, &player, false).await;
 		pvp::team::change_to(self, player_to_remove, None).await;
-		self.remove_creature(&player_to_remove.id).await;
-		self.broadcast(&pvp::map_head::create_toggle_packet(&player, false), None).await;
+		self.despawn_creatures().await;
 	}
 
-	async fn remove_creature(&self, creature_id: &CreatureId) {
-		//this is a shortcut, as the creature technically still exists
-		//the proper way to remove a creature requires updating all remaining creatures which is expensive on bandwidth
-		self.broadcast(&CreatureUpdate {
-			id: *creature_id,
-			health: Some(0.0), //makes the creature intangible
-			affiliation: Some(Affiliation::Neutral), //ensures it doesnt show up on the map
-			..Default::default()
-		}, None).await;
+	///cubeworld has no way to remove a single creature.
+	///instead, clients discard every creature that didn't receive an update in between 2 [ServerTick]s.
+	///this is expensive on bandwidth, but it's the only way to prevent leftover creatures (and their ids) from haunting everyone
+	async fn despawn_creatures(&self) {
+		let remaining_players = self.players.read().await.clone(); //reload_creatures needs to acquire this lock as well
+
+		remaining_players
+			.iter()
+			.map(|player| self.reload_creatures(player))
+			.pipe(join_all)
+			.await;
 	}
 
-	async fn read_packets_forever(&self, source: &Player, mut reader: BufReader<OwnedReadHalf>) {
+	async fn read_packets_forever(self: &Arc<Self>, source: &Player, mut reader: BufReader<OwnedReadHalf>) {
 		loop {
 			let future = self.process1packet(source, &mut reader);
 
@@ -313,8 +473,15 @@ impl Server {
 		}
 	}
 	
-	async fn process1packet(&self, source: &Player, reader: &mut BufReader<OwnedReadHalf>) -> io::Result<()> {
-		match reader.read_id().await? {
+	async fn process1packet(self: &Arc<Self>, source: &Player, reader: &mut BufReader<OwnedReadHalf>) -> io::Result<()> {
+		let packet_id = reader.read_id().await?;
+		self.metrics.count_received(&packet_id);
+
+		if !self.addons.flood_protection.allows(self, source, &packet_id).await {
+			return skip_packet(reader, packet_id).await;
+		}
+
+		match packet_id {
 			CreatureUpdate       ::ID => reader.read_packet::<CreatureUpdate       >().await?.pipe(|packet| self.handle_packet(source, packet)).await,
 			CreatureAction       ::ID => reader.read_packet::<CreatureAction       >().await?.pipe(|packet| self.handle_packet(source, packet)).await,
 			Hit                  ::ID => reader.read_packet::<Hit                  >().await?.pipe(|packet| self.handle_packet(source, packet)).await,
@@ -333,8 +500,14 @@ impl Server {
 //todo: way too much pvp stuff in here
 //todo: status effects (including team hearts)
 async fn send_existing_creatures(server: &Server, player: &Player) {
-	pvp::team::display::reload(player, &[]).await;
 	let own_team = player.addon_data.read().await.team;
+	let team_members =
+		if let Some(team) = own_team {
+			pvp::team::get_members(server, team).await
+		} else {
+			vec![]
+		};
+	pvp::team::display::reload(player, &team_members).await;
 	server
 		.players
 		.read()
@@ -356,7 +529,8 @@ async fn send_existing_creatures(server: &Server, player: &Player) {
 					packet.affiliation = Some(if is_teammate { Affiliation::Player } else { Affiliation::Enemy });
 					packet.rarity = Some(if is_teammate { 0 } else { 4 });
 				});
-			let map_head = map_head::create(&character, existing_player.id);
+			let map_head = map_head::create(&character, existing_player.id)
+				.tap_mut(|packet| packet.affiliation = Some(if is_teammate { Affiliation::Neutral } else { Affiliation::Player }));
 			drop(character);
 
 			player.send_ignoring(&creature_update).await;
@@ -368,18 +542,13 @@ async fn send_existing_creatures(server: &Server, player: &Player) {
 	server.addons.npcs.load_npcs(player).await;
 }
 
-fn configure_stream(stream: TcpStream) -> io::Result<(BufReader<OwnedReadHalf>, WriteHalf<SimplexStream>, JoinHandle<()>)>{
+fn configure_stream(stream: TcpStream, compression: Arc<CompressionStatistics>) -> io::Result<(BufReader<OwnedReadHalf>, Outbound, JoinHandle<()>)>{
 	stream.set_nodelay(true)?;
 
-	let (tcp_read, mut tcp_write) = stream.into_split();
-	let (mut sim_read, sim_write) = simplex(1_000_000);
-	let buf_read = BufReader::new(tcp_read);
-	let join_handle = tokio::spawn(async move {
-		_ = copy(&mut sim_read, &mut tcp_write).await;
-	});
-	
+	let (tcp_read, tcp_write) = stream.into_split();
+	let (outbound, join_handle) = Outbound::new(tcp_write, compression);
 
-	Ok((buf_read, sim_write, join_handle))
+	Ok((BufReader::new(tcp_read), outbound, join_handle))
 }
 
 async fn check_version(reader: &mut impl ReadPacket, writer: &mut impl WritePacket<ProtocolVersion>) -> io::Result<()> {
@@ -395,15 +564,30 @@ async fn check_version(reader: &mut impl ReadPacket, writer: &mut impl WritePack
 	Ok(())
 }
 
-async fn read_character_data(reader: &mut impl ReadPacket) -> io::Result<(CreatureUpdate, Creature)> {
+///reads a packet from the client without handling it
+async fn skip_packet(reader: &mut BufReader<OwnedReadHalf>, packet_id: packet::Id) -> io::Result<()> {
+	match packet_id {
+		CreatureUpdate       ::ID => _ = reader.read_packet::<CreatureUpdate       >().await?,
+		CreatureAction       ::ID => _ = reader.read_packet::<CreatureAction       >().await?,
+		Hit                  ::ID => _ = reader.read_packet::<Hit                  >().await?,
+		StatusEffect         ::ID => _ = reader.read_packet::<StatusEffect         >().await?,
+		Projectile           ::ID => _ = reader.read_packet::<Projectile           >().await?,
+		ChatMessageFromClient::ID => _ = reader.read_packet::<ChatMessageFromClient>().await?,
+		AreaRequest::<Zone>  ::ID => _ = reader.read_packet::<AreaRequest<Zone>    >().await?,
+		AreaRequest::<Region>::ID => _ = reader.read_packet::<AreaRequest<Region>  >().await?,
+		_unexpected_packet_id => return Err(InvalidData.into())
+	}
+
+	Ok(())
+}
+
+async fn read_character_data(reader: &mut impl ReadPacket) -> io::Result<Creature> {
 	if reader.read_id().await? != CreatureUpdate::ID {
 		return Err(InvalidData.into());
 	}
 
 	let creature_update = reader.read_packet::<CreatureUpdate>().await?;
 
-	let character = Creature::maybe_from(&creature_update)
-		.ok_or_else::<io::Error, _>(||InvalidInput.into())?;
-
-	Ok((creature_update, character))
+	Creature::maybe_from(&creature_update)
+		.ok_or_else(||InvalidInput.into())
 }
\ No newline at end of file
@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, HashMap};
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+
+use ::config::{Config, ConfigError, Value};
+use serde::Deserialize;
+
+use protocol::nalgebra::Point3;
+
+use crate::addon::afk::AfkConfig;
+use crate::addon::balancing::BalanceConfigValues;
+use crate::addon::chat_moderation::ChatConfig;
+use crate::addon::models;
+use crate::addon::name_policy::NamesConfig;
+use crate::addon::voting::VotingConfig;
+use crate::addon::flood_protection::PacketLimits;
+use crate::addon::permissions::{self, command_permission, Role, ADMIN_NOTIFICATIONS, ANTI_CHEAT_BYPASS};
+use crate::logging::LogConfig;
+use crate::server::PORT;
+
+///produces a fresh [ServerConfig] from the same sources every time it gets called
+pub type ConfigLoader = Box<dyn Fn() -> Result<ServerConfig, ConfigError> + Send + Sync>;
+
+///the complete config file, every key in here is documented and set to its default value
+pub const EXAMPLE: &str = include_str!("../../config.example.toml");
+
+///everything that can be set in the config file (or via `BERLD_` prefixed environment variables).
+///missing keys fall back to the defaults found in [EXAMPLE]
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+	pub bind: Vec<Ipv4Addr>, //cubeworld is incapable of ipv6 networking
+	pub port: u16,
+	pub data_dir: PathBuf,
+	pub seed: i32,
+	pub motd: String,
+	pub default_permissions: Vec<String>,
+	pub discord_bot_token: Option<String>, //none disables the discord integration
+	pub discord_public_channel_id: Option<u64>,
+	pub discord_admin_channel_id: Option<u64>,
+	pub listforgeapi_port: Option<u16>, //none disables the listforge api
+	pub admin_api_token: Option<String>, //none disables the admin api
+	pub rcon_port: Option<u16>, //none disables rcon
+	pub rcon_password: Option<String>,
+	pub slots: i32,
+	pub queue_size: usize, //0 disables the queue
+	pub handshake_timeout: u64, //seconds
+	pub max_connections_per_ip: usize,
+	pub connections_per_minute_per_ip: u32,
+	pub whitelist_message: String,
+	pub report_cooldown: u64, //seconds
+	pub name: String,
+	pub discord_invite: String,
+	pub warps: HashMap<String, Point3<i64>>,
+	pub roles: BTreeMap<String, Role>,
+	pub models: HashMap<String, [i64; 3]>,
+	pub balance: BalanceConfigValues,
+	pub chat: ChatConfig,
+	pub names: NamesConfig,
+	pub voting: VotingConfig,
+	pub afk: AfkConfig,
+	pub packet_limits: PacketLimits,
+	pub log: LogConfig
+}
+
+impl Default for ServerConfig {
+	fn default() -> Self {
+		Self {
+			bind: vec![Ipv4Addr::UNSPECIFIED],
+			port: PORT,
+			data_dir: ".".into(),
+			seed: 0,
+			motd: "welcome to berld".into(),
+			default_permissions: [
+				"who", "player", "xp", "level", "countdown", "warp", "gear", "give", "tp", "team", "act", "report", "votekick", "vote", "poll"
+			].map(command_permission).into(),
+			discord_bot_token: None,
+			discord_public_channel_id: None,
+			discord_admin_channel_id: None,
+			listforgeapi_port: None,
+			admin_api_token: None,
+			rcon_port: None,
+			rcon_password: None,
+			slots: 32,
+			queue_size: 8,
+			handshake_timeout: 10,
+			max_connections_per_ip: 4,
+			connections_per_minute_per_ip: 20,
+			whitelist_message: "this server is private at the moment".into(),
+			report_cooldown: 60,
+			name: "berld".into(),
+			discord_invite: String::new(),
+			warps: HashMap::new(),
+			roles: [
+				("admin"    , vec!["*".into()]),
+				("moderator", ["kick", "who_ip", "tempban", "banlist", "mute", "unmute", "profile", "note", "reports"]
+					.map(command_permission)
+					.into_iter()
+					.chain([ADMIN_NOTIFICATIONS.into()])
+					.collect()),
+				("immune"   , vec![ANTI_CHEAT_BYPASS.into()])
+			]
+				.map(|(name, permissions)| (name.into(), Role { permissions, ..Default::default() }))
+				.into(),
+			models: HashMap::new(),
+			balance: BalanceConfigValues::default(),
+			chat: ChatConfig::default(),
+			names: NamesConfig::default(),
+			voting: VotingConfig::default(),
+			afk: AfkConfig::default(),
+			packet_limits: PacketLimits::default(),
+			log: LogConfig::default()
+		}
+	}
+}
+
+impl ServerConfig {
+	pub fn load(config: Config) -> Result<Self, ConfigError> {
+		//these would otherwise fail with nothing but a generic unknown field error
+		for (key, role) in [("admin_password", "admin"), ("ac_immune_password", "immune")] {
+			if config.get::<Value>(key).is_ok() {
+				return invalid(format!("{key} has been replaced by roles, remove it and set `password` in [roles.{role}] instead"));
+			}
+		}
+
+		let instance: Self = config.try_deserialize()?;
+		instance.validate()?;
+		Ok(instance)
+	}
+
+	///catches everything that would otherwise only blow up at runtime
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.bind.is_empty() {
+			return invalid("bind must contain at least 1 address");
+		}
+
+		if self.port == 0 {
+			return invalid("port must be within 1..=65535");
+		}
+
+		if self.data_dir.exists() && !self.data_dir.is_dir() {
+			return invalid(format!("data_dir '{}' is not a directory", self.data_dir.display()));
+		}
+
+		if self.slots < 1 {
+			return invalid("slots must be at least 1");
+		}
+
+		for (key, value) in [
+			("handshake_timeout"            , self.handshake_timeout),
+			("max_connections_per_ip"       , self.max_connections_per_ip as u64),
+			("connections_per_minute_per_ip", u64::from(self.connections_per_minute_per_ip))
+		] {
+			if value == 0 {
+				return invalid(format!("{key} must be at least 1"));
+			}
+		}
+
+		if let Some(port) = self.listforgeapi_port {
+			if port == 0 {
+				return invalid("listforgeapi_port must be within 1..=65535");
+			}
+			if port == self.port {
+				return invalid(format!("listforgeapi_port must not be the game port ({port})"));
+			}
+		}
+
+		if let Some(token) = &self.admin_api_token {
+			if self.listforgeapi_port.is_none() {
+				return invalid("admin_api_token requires listforgeapi_port to be set");
+			}
+			if token.is_empty() {
+				return invalid("admin_api_token must not be empty");
+			}
+		}
+
+		if let Some(port) = self.rcon_port {
+			if port == 0 {
+				return invalid("rcon_port must be within 1..=65535");
+			}
+			if port == self.port || Some(port) == self.listforgeapi_port {
+				return invalid(format!("rcon_port must not be the game or api port ({port})"));
+			}
+			match &self.rcon_password {
+				None => return invalid("rcon_password is required when rcon_port is set"),
+				Some(password) if password.is_empty() => return invalid("rcon_password must not be empty"),
+				Some(_) => {}
+			}
+		}
+
+		if self.discord_bot_token.is_some() {
+			for (key, channel_id) in [
+				("discord_public_channel_id", self.discord_public_channel_id),
+				("discord_admin_channel_id" , self.discord_admin_channel_id)
+			] {
+				match channel_id {
+					None    => return invalid(format!("{key} is required when discord_bot_token is set")),
+					Some(0) => return invalid(format!("{key} must not be 0")),
+					Some(_) => ()
+				}
+			}
+		}
+
+		for filename in self.models.keys() {
+			let path = Path::new(filename);
+			if !path.is_file() {
+				return invalid(format!("model file '{filename}' does not exist"));
+			}
+			if !path.extension().is_some_and(|extension| extension == "vox" || extension == "zox") {
+				return invalid(format!("model file '{filename}' is neither .vox nor .zox"));
+			}
+			//parsed here rather than only once applied, so a broken file can't leave a reload half done
+			if let Err(error) = models::parse_model(filename) {
+				return invalid(format!("model file '{filename}' can't be parsed: {error}"));
+			}
+		}
+
+		permissions::validate(self)?;
+		self.balance.validate()?;
+		self.chat.validate()?;
+		self.names.validate()?;
+		self.voting.validate()?;
+		self.afk.validate()?;
+		self.packet_limits.validate()?;
+		self.log.validate()
+	}
+}
+
+pub fn invalid<T>(message: impl Into<String>) -> Result<T, ConfigError> {
+	Err(ConfigError::Message(message.into()))
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use tokio::sync::Mutex;
+
+use crate::server::config::ServerConfig;
+use crate::server::token_bucket::TokenBucket;
+
+///guards against a single ip hogging resources by opening lots of connections
+pub struct ConnectionLimit {
+	max_per_ip: usize,
+	per_minute: f64,
+	addresses: Mutex<HashMap<IpAddr, AddressState>>
+}
+
+struct AddressState {
+	open: usize,
+	attempts: TokenBucket
+}
+
+impl ConnectionLimit {
+	pub fn new(config: &ServerConfig) -> Self {
+		Self {
+			max_per_ip: config.max_connections_per_ip,
+			per_minute: f64::from(config.connections_per_minute_per_ip),
+			addresses: Mutex::default()
+		}
+	}
+
+	///every successful call must eventually be followed up by [Self::close]
+	pub async fn try_open(&self, ip: IpAddr) -> Result<(), &'static str> {
+		let mut addresses = self.addresses.lock().await;
+		let state = addresses
+			.entry(ip)
+			.or_insert_with(|| AddressState {
+				open: 0,
+				attempts: TokenBucket::new(self.per_minute, self.per_minute / 60.0)
+			});
+
+		if !state.attempts.try_take() {
+			return Err("too many connection attempts");
+		}
+
+		if state.open >= self.max_per_ip {
+			return Err("too many open connections");
+		}
+
+		state.open += 1;
+		Ok(())
+	}
+
+	pub async fn close(&self, ip: IpAddr) {
+		let mut addresses = self.addresses.lock().await;
+		if let Some(state) = addresses.get_mut(&ip) {
+			state.open -= 1;
+		}
+
+		//forget addresses that have nothing left to remember
+		addresses.retain(|_ip, state| state.open > 0 || !state.attempts.is_full());
+	}
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use protocol::packet::FromClient;
 
 use crate::server::player::Player;
@@ -12,5 +14,5 @@ mod zone_request;
 mod region_request;
 
 pub trait HandlePacket<Packet: FromClient> {
-	async fn handle_packet(&self, source: &Player, packet: Packet);
+	async fn handle_packet(self: &Arc<Self>, source: &Player, packet: Packet);
 }
\ No newline at end of file
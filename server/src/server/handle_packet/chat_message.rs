@@ -1,22 +1,23 @@
-use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Arc;
 
-use colour::{cyan, white_ln};
+use tracing::info;
 
 use protocol::packet::ChatMessageFromClient;
 use protocol::packet::world_update::sound::Kind::*;
 use crate::addon::command_manager::CommandResult;
-use crate::addon::{play_sound_for_everyone, play_sound_at_player};
+use crate::addon::{afk, play_sound_for_everyone, play_sound_at_player};
+use crate::addon::permissions::Issuer;
+use crate::addon::rcon::Event;
 
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<ChatMessageFromClient> for Server {
-	async fn handle_packet(&self, source: &Player, packet: ChatMessageFromClient) {
+	async fn handle_packet(self: &Arc<Self>, source: &Player, mut packet: ChatMessageFromClient) {
 		let source_name = source.character.read().await.name.clone();
 
-		cyan!("{}: ", source_name);
-		white_ln!("{}", packet.text);
+		afk::on_activity(source).await;
 
 		let callback = |command_result| { command_callback(command_result, source) };
 
@@ -25,13 +26,18 @@ impl HandlePacket<ChatMessageFromClient> for Server {
 			.command_manager
 			.on_message(
 				self,
-				Some(source), //TODO: use a custom enum so `admin` doesnt have to be passed here
-				source.admin.load(Relaxed),
+				Issuer::Player(source),
 				&packet.text,
 				'/',
 				callback
 			).await;
-		if is_command { return; }
+		if is_command { return; } //logged by the command manager, without parameters as they might contain passwords
+
+		info!(target: "chat", "{source_name}: {}", packet.text);
+
+		let Some(text) = self.addons.chat_moderation.moderate(self, source, &source_name, &packet.text).await
+			else { return };
+		packet.text = text;
 
 		let echo = packet.into_reverse(source.id);
 		self.broadcast(&echo, None).await;
@@ -47,6 +53,7 @@ impl HandlePacket<ChatMessageFromClient> for Server {
 				),
 				false
 			).await;
+		self.addons.rcon.publish(Event::Chat { name: source_name, text: echo.text });
 	}
 }
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use protocol::nalgebra::Vector3;
 use protocol::packet::common::item::Material::*;
 use protocol::packet::common::Item;
@@ -11,7 +13,7 @@ use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<CreatureAction> for Server {
-	async fn handle_packet(&self, source: &Player, packet: CreatureAction) {
+	async fn handle_packet(self: &Arc<Self>, source: &Player, packet: CreatureAction) {
 		#[expect(clippy::match_same_arms, reason = "todo")]
 		match packet.kind {
 			Bomb => {
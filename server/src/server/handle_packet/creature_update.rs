@@ -1,9 +1,12 @@
-use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use tracing::warn;
 
 use protocol::packet::CreatureUpdate;
 
-use crate::addon::{anti_cheat, kill_feed, pvp};
+use crate::addon::{afk, anti_cheat, kill_feed, pvp};
 use crate::addon::fix_cutoff_animations;
+use crate::addon::permissions::{Issuer, ANTI_CHEAT_BYPASS};
 use crate::addon::traffic_filter::filter;
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
@@ -11,15 +14,39 @@ use crate::server::Server;
 
 impl HandlePacket<CreatureUpdate> for Server {
 	#[expect(clippy::significant_drop_tightening, reason = "false positive")]
-	async fn handle_packet(&self, source: &Player, mut packet: CreatureUpdate) {
-		if let Err(message) = anti_cheat::inspect_creature_update(source, &packet).await && !source.ac_immune.load(Ordering::Relaxed) {
-			self.kick(source, message).await;
-			return;
+	async fn handle_packet(self: &Arc<Self>, source: &Player, mut packet: CreatureUpdate) {
+		if let Err(message) = anti_cheat::inspect_creature_update(source, &packet).await {
+			let immune = self.addons.permissions.allows(Issuer::Player(source), ANTI_CHEAT_BYPASS).await;
+			warn!(violation = %message, immune, "anti-cheat");
+
+			if !immune {
+				//every violation message starts with the name of the offending property
+				let rule = message.split(' ').next().unwrap_or_default();
+				self.metrics.count_anti_cheat_kick(rule).await;
+				self.kick(source, message).await;
+				return;
+			}
+			source.addon_data.write().await.anti_cheat_data.note_near_miss(format!("{message} (immune)"));
+		}
+
+		let current_name = source.character.read().await.name.clone();
+		if let Some(name) = &packet.name && *name != current_name {
+			match self.addons.name_policy.check(self, name, Some(source)).await {
+				Ok(accepted) => {
+					self.addons.chat_moderation.on_rename(source, &current_name).await;
+					packet.name = Some(accepted);
+				}
+				Err(reason) => {
+					packet.name = None; //keeps the current name
+					source.notify(reason).await;
+				}
+			}
 		}
 
 		self.addons.balancing.track_airtime(source).await;
 		pvp::on_creature_update(self, source, &packet).await;
 		kill_feed::on_creature_update(self, source, &packet).await;
+		afk::on_creature_update(source, &packet).await;
 
 		let mut character = source.character.write().await;
 		let snapshot = character.clone();
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use tap::Tap as _;
 
 use protocol::{packet::creature_update::equipment::Slot, utils::constants::CombatClass};
@@ -19,7 +21,7 @@ use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<Hit> for Server {
-	async fn handle_packet(&self, source: &Player, mut packet: Hit) {
+	async fn handle_packet(self: &Arc<Self>, source: &Player, mut packet: Hit) {
 		let Some(target) = self.find_player_by_id(packet.target).await
 			else { return; };//can happen when the target disconnected in this moment
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use protocol::packet::projectile::Kind::*;
 use protocol::packet::world_update::Sound;
 use protocol::utils::sound_position_of;
@@ -11,7 +13,7 @@ use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<Projectile> for Server {
-	async fn handle_packet(&self, source: &Player, packet: Projectile) {
+	async fn handle_packet(self: &Arc<Self>, source: &Player, packet: Projectile) {
 		let mut world_update = WorldUpdate::from(packet.clone()); //todo: this clone should be avoidable
 
 		if let Some(sound) = get_sound(source, packet).await {
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use protocol::packet::AreaRequest;
 use protocol::packet::area_request::Region;
 use crate::server::handle_packet::HandlePacket;
@@ -5,7 +7,7 @@ use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<AreaRequest<Region>> for Server {
-	async fn handle_packet(&self, _source: &Player, _packet: AreaRequest<Region>) {
+	async fn handle_packet(self: &Arc<Self>, _source: &Player, _packet: AreaRequest<Region>) {
 
 	}
 }
\ No newline at end of file
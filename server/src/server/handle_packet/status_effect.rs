@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use protocol::packet::{StatusEffect, WorldUpdate};
 use protocol::packet::status_effect::Kind::*;
 use protocol::packet::world_update::sound::Kind::*;
@@ -8,7 +10,7 @@ use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<StatusEffect> for Server {
-	async fn handle_packet(&self, source: &Player, mut packet: StatusEffect) {
+	async fn handle_packet(self: &Arc<Self>, source: &Player, mut packet: StatusEffect) {
 		match packet.kind {
 			Poison => {
 				let Some(target) = self.find_player_by_id(packet.target).await
@@ -21,7 +23,7 @@ impl HandlePacket<StatusEffect> for Server {
 			}
 			ManaShield => {
 				if packet.duration == 30000 { //client echo's the packet, so we must make sure not to cause a feedback loop
-					self.addons.balancing.adjust_manashield(&mut packet);
+					self.addons.balancing.adjust_manashield(&mut packet).await;
 					source.send_ignoring(&WorldUpdate::from(packet.clone())).await;
 				} else {
 					source.notify(format!("manashield: {} ({} ms)", packet.modifier, packet.duration)).await;
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::io::ErrorKind::UnexpectedEof;
+use std::net::SocketAddr;
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{self, AsyncBufReadExt, BufReader};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::select;
+use tokio::sync::{Mutex, Notify};
+use tokio::time::{sleep, timeout};
+
+use protocol::packet::*;
+use protocol::utils::io_extensions::ReadPacket;
+use protocol::Packet;
+
+use crate::addon::name_policy::taken_names;
+use crate::addon::permissions::{Issuer, QUEUE_BYPASS};
+use crate::server::config::ServerConfig;
+use crate::server::player::Player;
+use crate::server::{skip_packet, Server, TIMEOUT};
+
+///packet limits don't apply in the queue, so this keeps passwords from being guessed quickly
+const LOGIN_DELAY: Duration = Duration::from_secs(1);
+
+pub struct JoinQueue {
+	slots: usize,
+	capacity: usize, //0 disables the queue
+	waiting: Mutex<VecDeque<Arc<Player>>>,
+	changed: Notify //a slot got freed or someone left the queue
+}
+
+impl JoinQueue {
+	pub fn new(config: &ServerConfig) -> Self {
+		Self {
+			slots: config.slots as usize,
+			capacity: config.queue_size,
+			waiting: Mutex::default(),
+			changed: Notify::new()
+		}
+	}
+
+	pub async fn waiting(&self) -> usize {
+		self.waiting.lock().await.len()
+	}
+
+	pub fn on_player_removed(&self) {
+		self.changed.notify_waiters();
+	}
+}
+
+impl Server {
+	///everyone online followed by everyone in the queue, taken at the same time so nobody gets missed while moving from one to the other
+	pub async fn joined_and_queued(&self) -> Vec<Arc<Player>> {
+		let players = self.players.read().await;
+		let waiting = self.join_queue.waiting.lock().await;
+
+		players.iter().chain(waiting.iter()).cloned().collect()
+	}
+
+	///whether a new connection should be turned down right away, as neither a slot nor a place in the queue is available.
+	///only roles of the address count towards [QUEUE_BYPASS] here, those logged into need a place in the queue first
+	pub(super) async fn is_full(&self, address: SocketAddr) -> bool {
+		let queue = &self.join_queue;
+		if self.addons.permissions.allows_address(address.ip(), QUEUE_BYPASS).await {
+			return false;
+		}
+
+		let players = self.players.read().await.len();
+		let waiting = queue.waiting.lock().await.len();
+
+		players >= queue.slots && waiting >= queue.capacity
+	}
+
+	///adds the player to the player list as soon as a slot is available.
+	///until then, the client is told its position in the queue and everything it sends (apart from character changes and `/login`) is discarded.
+	///players permitted to [QUEUE_BYPASS] don't have to wait.
+	///returns false if the name got refused on the way in, in which case the player has been told why
+	pub(super) async fn take_slot(self: &Arc<Self>, player: &Arc<Player>, reader: &mut BufReader<OwnedReadHalf>) -> io::Result<bool> {
+		let queue = &self.join_queue;
+		let mut reported_position = None;
+		let mut renamed = None;
+
+		let result = loop {
+			let mut changed = pin!(queue.changed.notified());
+			changed.as_mut().enable(); //ensures no notification gets missed in between checking and waiting
+			let bypasses = self.addons.permissions.allows(Issuer::Player(player), QUEUE_BYPASS).await;
+
+			let mut players = self.players.write().await;
+			let mut waiting = queue.waiting.lock().await;
+			let position = waiting
+				.iter()
+				.position(|queued| Arc::ptr_eq(queued, player))
+				.unwrap_or_else(|| {
+					waiting.push_back(Arc::clone(player));
+					waiting.len() - 1
+				});
+
+			if bypasses || (position == 0 && players.len() < queue.slots) {
+				waiting.remove(position);
+				queue.changed.notify_waiters(); //everyone behind moves up
+
+				//the name was checked during the handshake, but it may have been changed or taken by someone else since.
+				//checking again while holding both locks ensures nobody else can take it in between
+				let others = players.iter().chain(waiting.iter()).cloned().collect::<Vec<_>>();
+				let taken = taken_names(&others, Some(player)).await;
+				let name = player.character.read().await.name.clone();
+				match self.addons.name_policy.check_against(&name, &taken).await {
+					Ok(accepted) if accepted == name => {}
+					Ok(accepted) => {
+						player.character.write().await.name.clone_from(&accepted);
+						renamed = Some((name, accepted));
+					}
+					Err(reason) => break Ok(Err(reason))
+				}
+
+				players.push(Arc::clone(player));
+				break Ok(Ok(()));
+			}
+			drop((players, waiting));
+
+			if reported_position != Some(position) {
+				player.notify(format!("the server is full, you are #{} in the queue", position + 1)).await;
+				reported_position = Some(position);
+			}
+
+			//reading a packet must not be interrupted halfway through, so only waiting for data is raced against the notification
+			let data_available = select! {
+				() = changed => false,
+				result = wait_for_data(reader) => {
+					if let Err(error) = result {
+						break Err(error);
+					}
+					true
+				}
+			};
+
+			if data_available && let Err(error) = process_queued_packet(self, player, reader).await {
+				break Err(error);
+			}
+		};
+
+		if result.is_err() {
+			queue.waiting.lock().await.retain(|queued| !Arc::ptr_eq(queued, player));
+			queue.on_player_removed(); //the next in line might be able to join now
+		}
+
+		match result? {
+			Ok(()) => {
+				if let Some((requested, accepted)) = renamed {
+					player.notify(format!("someone named {requested} is online already, so you are {accepted} here")).await;
+				}
+				Ok(true)
+			}
+			Err(reason) => {
+				player.notify(reason).await;
+				Ok(false)
+			}
+		}
+	}
+}
+
+async fn wait_for_data(reader: &mut BufReader<OwnedReadHalf>) -> io::Result<()> {
+	let buffer = timeout(TIMEOUT, reader.fill_buf()).await??;
+
+	if buffer.is_empty() {
+		return Err(UnexpectedEof.into());
+	}
+
+	Ok(())
+}
+
+async fn process_queued_packet(server: &Arc<Server>, player: &Player, reader: &mut BufReader<OwnedReadHalf>) -> io::Result<()> {
+	match reader.read_id().await? {
+		CreatureUpdate       ::ID => {
+			let packet = reader.read_packet::<CreatureUpdate>().await?;
+			let old_name = player.character.read().await.name.clone();
+			if packet.name.as_ref().is_some_and(|name| *name != old_name) {
+				server.addons.chat_moderation.on_rename(player, &old_name).await;
+			}
+			player.character.write().await.update(&packet);
+		},
+		ChatMessageFromClient::ID => {
+			let packet = reader.read_packet::<ChatMessageFromClient>().await?;
+			//logging in might grant [QUEUE_BYPASS]
+			if packet.text.to_lowercase().starts_with("/login ") {
+				server.addons.command_manager.on_message(server, Issuer::Player(player), &packet.text, '/', async |result| {
+					player.notify(result.map_or_else(Into::into, Option::unwrap_or_default)).await;
+				}).await;
+				sleep(LOGIN_DELAY).await;
+			}
+		},
+		other => skip_packet(reader, other).await?
+	}
+
+	Ok(())
+}
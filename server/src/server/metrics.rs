@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::Duration;
+
+use tokio::runtime::Handle;
+use tokio::sync::Mutex;
+
+use protocol::Packet;
+use protocol::packet::{self, *};
+use protocol::packet::area_request::{Region, Zone};
+use protocol::utils::compression::CompressionStatistics;
+
+use crate::addon::flood_protection::LimitedPacket;
+use crate::server::Server;
+
+//chat messages share their id across both directions
+const PACKET_NAMES: [(packet::Id, &str); 17] = [
+	(CreatureUpdate       ::ID, "creature_update"),
+	(MultiCreatureUpdate  ::ID, "multi_creature_update"),
+	(ServerTick           ::ID, "server_tick"),
+	(AirshipTraffic       ::ID, "airship_traffic"),
+	(WorldUpdate          ::ID, "world_update"),
+	(IngameDatetime       ::ID, "ingame_datetime"),
+	(CreatureAction       ::ID, "creature_action"),
+	(Hit                  ::ID, "hit"),
+	(StatusEffect         ::ID, "status_effect"),
+	(Projectile           ::ID, "projectile"),
+	(ChatMessageFromServer::ID, "chat_message"),
+	(AreaRequest::<Zone>  ::ID, "zone_request"),
+	(AreaRequest::<Region>::ID, "region_request"),
+	(MapSeed              ::ID, "map_seed"),
+	(ConnectionAcceptance ::ID, "connection_acceptance"),
+	(ProtocolVersion      ::ID, "protocol_version"),
+	(ConnectionRejection  ::ID, "connection_rejection")
+];
+
+const LATENCY_BUCKETS: [f64; 8] = [0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0]; //seconds
+
+#[derive(Debug)]
+pub struct PacketCounters([AtomicU64; PACKET_NAMES.len()]);
+
+impl Default for PacketCounters {
+	fn default() -> Self {
+		Self([const { AtomicU64::new(0) }; PACKET_NAMES.len()])
+	}
+}
+
+impl PacketCounters {
+	pub fn count(&self, packet_id: &packet::Id, amount: u64) {
+		if let Some(index) = PACKET_NAMES.iter().position(|(id, _name)| id == packet_id) {
+			self.0[index].fetch_add(amount, Relaxed);
+		}
+	}
+
+	fn samples(&self) -> impl Iterator<Item = (String, u64)> {
+		PACKET_NAMES
+			.iter()
+			.zip(&self.0)
+			.map(|((_id, name), counter)| (format!("type=\"{name}\""), counter.load(Relaxed)))
+	}
+}
+
+#[derive(Default)]
+pub struct Metrics {
+	packets_received: PacketCounters,
+	packets_sent: Arc<PacketCounters>, //shared with every [crate::server::player::Player]
+	compression: Arc<CompressionStatistics>, //shared with every [crate::server::outbound::Outbound]
+	broadcast_durations: Histogram,
+	commands: Mutex<BTreeMap<&'static str, u64>>,
+	anti_cheat_kicks: Mutex<BTreeMap<String, u64>>
+}
+
+impl Metrics {
+	pub fn count_received(&self, packet_id: &packet::Id) {
+		self.packets_received.count(packet_id, 1);
+	}
+
+	pub fn count_sent(&self, packet_id: &packet::Id, amount: u64) {
+		self.packets_sent.count(packet_id, amount);
+	}
+
+	pub fn packets_sent(&self) -> Arc<PacketCounters> {
+		Arc::clone(&self.packets_sent)
+	}
+
+	pub fn compression(&self) -> Arc<CompressionStatistics> {
+		Arc::clone(&self.compression)
+	}
+
+	pub fn observe_broadcast(&self, duration: Duration) {
+		self.broadcast_durations.observe(duration);
+	}
+
+	pub async fn count_command(&self, literal: &'static str) {
+		*self.commands.lock().await.entry(literal).or_default() += 1;
+	}
+
+	///`rule` is the name of the property that failed inspection
+	pub async fn count_anti_cheat_kick(&self, rule: &str) {
+		*self.anti_cheat_kicks.lock().await.entry(rule.to_owned()).or_default() += 1;
+	}
+}
+
+#[derive(Default)]
+struct Histogram {
+	buckets: [AtomicU64; LATENCY_BUCKETS.len()], //not cumulative, unlike the rendered output
+	count: AtomicU64,
+	sum_nanos: AtomicU64
+}
+
+impl Histogram {
+	fn observe(&self, duration: Duration) {
+		let seconds = duration.as_secs_f64();
+		if let Some(index) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+			self.buckets[index].fetch_add(1, Relaxed);
+		}
+		self.count.fetch_add(1, Relaxed);
+		self.sum_nanos.fetch_add(duration.as_nanos() as u64, Relaxed);
+	}
+}
+
+impl Server {
+	///everything in the prometheus text exposition format
+	pub async fn render_metrics(&self) -> String {
+		let mut output = String::new();
+		let players = self.players.read().await.clone(); //holding this lock while rendering would block joins
+
+		write_metric(&mut output, "berld_players", "gauge", "players currently online", [(String::new(), players.len() as u64)]);
+		write_metric(&mut output, "berld_queued_players", "gauge", "players waiting for a free slot", [(String::new(), self.join_queue.waiting().await as u64)]);
+
+		write_metric(&mut output, "berld_packets_received_total", "counter", "packets received from clients", self.metrics.packets_received.samples());
+		write_metric(&mut output, "berld_packets_sent_total", "counter", "packets sent to clients", self.metrics.packets_sent.samples());
+
+		let compression = &self.metrics.compression;
+		write_metric(&mut output, "berld_uncompressed_bytes_total", "counter", "size of compressed packets before compression", [(String::new(), compression.uncompressed_bytes.load(Relaxed))]);
+		write_metric(&mut output, "berld_compressed_bytes_total", "counter", "size of compressed packets after compression", [(String::new(), compression.compressed_bytes.load(Relaxed))]);
+
+		let backlogs = players.iter().map(|player| player.backlog.load(Relaxed));
+		write_metric(&mut output, "berld_outbound_queued_bytes", "gauge", "bytes waiting to be sent, summed over all players", [(String::new(), backlogs.clone().sum())]);
+		write_metric(&mut output, "berld_outbound_queued_bytes_max", "gauge", "bytes waiting to be sent to the most backed up player", [(String::new(), backlogs.max().unwrap_or(0))]);
+
+		write_histogram(&mut output, "berld_broadcast_duration_seconds", "time taken to send a packet to every player", &self.metrics.broadcast_durations);
+
+		let commands = self.metrics.commands.lock().await.iter().map(|(literal, count)| (format!("command=\"{literal}\""), *count)).collect::<Vec<_>>();
+		write_metric(&mut output, "berld_commands_total", "counter", "commands executed", commands);
+
+		let kicks = self.metrics.anti_cheat_kicks.lock().await.iter().map(|(rule, count)| (format!("rule=\"{rule}\""), *count)).collect::<Vec<_>>();
+		write_metric(&mut output, "berld_anti_cheat_kicks_total", "counter", "players kicked by anti-cheat", kicks);
+
+		let flood = &self.addons.flood_protection.statistics;
+		let dropped = LimitedPacket::ALL.map(|kind| (format!("type=\"{}\"", kind.name()), flood.dropped[&kind].load(Relaxed)));
+		write_metric(&mut output, "berld_flood_dropped_packets_total", "counter", "packets dropped for exceeding a rate limit", dropped);
+		write_metric(&mut output, "berld_flood_warnings_total", "counter", "players warned for exceeding a rate limit", [(String::new(), flood.warnings.load(Relaxed))]);
+		write_metric(&mut output, "berld_flood_kicks_total", "counter", "players kicked for exceeding a rate limit too often", [(String::new(), flood.kicks.load(Relaxed))]);
+
+		let runtime = Handle::current().metrics();
+		write_metric(&mut output, "berld_tokio_alive_tasks", "gauge", "tasks currently alive in the runtime", [(String::new(), runtime.num_alive_tasks() as u64)]);
+		write_metric(&mut output, "berld_tokio_workers", "gauge", "worker threads of the runtime", [(String::new(), runtime.num_workers() as u64)]);
+		write_metric(&mut output, "berld_tokio_global_queue_depth", "gauge", "tasks waiting in the global queue", [(String::new(), runtime.global_queue_depth() as u64)]);
+
+		output
+	}
+}
+
+///`samples` are pairs of comma separated labels (without braces) and values
+fn write_metric(output: &mut String, name: &str, kind: &str, help: &str, samples: impl IntoIterator<Item = (String, u64)>) {
+	_ = writeln!(output, "# HELP {name} {help}");
+	_ = writeln!(output, "# TYPE {name} {kind}");
+
+	for (labels, value) in samples {
+		if labels.is_empty() {
+			_ = writeln!(output, "{name} {value}");
+		} else {
+			_ = writeln!(output, "{name}{{{labels}}} {value}");
+		}
+	}
+}
+
+fn write_histogram(output: &mut String, name: &str, help: &str, histogram: &Histogram) {
+	_ = writeln!(output, "# HELP {name} {help}");
+	_ = writeln!(output, "# TYPE {name} histogram");
+
+	let mut cumulative = 0;
+	for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+		cumulative += bucket.load(Relaxed);
+		_ = writeln!(output, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+	}
+
+	let count = histogram.count.load(Relaxed);
+	_ = writeln!(output, "{name}_bucket{{le=\"+Inf\"}} {count}");
+	_ = writeln!(output, "{name}_sum {}", Duration::from_nanos(histogram.sum_nanos.load(Relaxed)).as_secs_f64());
+	_ = writeln!(output, "{name}_count {count}");
+}
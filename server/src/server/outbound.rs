@@ -0,0 +1,77 @@
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::io::{self, simplex, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, SimplexStream, WriteHalf};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::task::JoinHandle;
+
+use protocol::utils::compression::{CompressionStatistics, MeasureCompression};
+
+const CAPACITY: usize = 1_000_000;
+
+///the sending half of a connection. writes land in a buffer which a background task forwards to the socket,
+///so a slow client can't hold up whoever is sending something to it
+#[derive(Debug)]
+pub struct Outbound {
+	buffer: WriteHalf<SimplexStream>,
+	backlog: Arc<AtomicU64>,
+	compression: Arc<CompressionStatistics>
+}
+
+impl Outbound {
+	///the returned task ends once the socket breaks or [Outbound] is shut down. aborting it closes the connection
+	pub fn new(socket: OwnedWriteHalf, compression: Arc<CompressionStatistics>) -> (Self, JoinHandle<()>) {
+		let (buffered, buffer) = simplex(CAPACITY);
+		let backlog = Arc::new(AtomicU64::default());
+
+		let join_handle = tokio::spawn(forward(buffered, socket, Arc::clone(&backlog)));
+
+		(Self { buffer, backlog, compression }, join_handle)
+	}
+
+	///amount of bytes written but not yet passed on to the socket
+	pub fn backlog(&self) -> Arc<AtomicU64> {
+		Arc::clone(&self.backlog)
+	}
+}
+
+async fn forward(mut buffered: impl AsyncRead + Unpin, mut socket: OwnedWriteHalf, backlog: Arc<AtomicU64>) {
+	let mut chunk = vec![0_u8; 8 * 1024];
+
+	while let Ok(length) = buffered.read(&mut chunk).await {
+		if length == 0 || socket.write_all(&chunk[..length]).await.is_err() {
+			break;
+		}
+
+		backlog.fetch_sub(length as u64, Relaxed);
+	}
+}
+
+impl MeasureCompression for Outbound {
+	fn compression_statistics(&self) -> Option<&CompressionStatistics> {
+		Some(&self.compression)
+	}
+}
+
+impl AsyncWrite for Outbound {
+	fn poll_write(mut self: Pin<&mut Self>, context: &mut Context<'_>, data: &[u8]) -> Poll<io::Result<usize>> {
+		let result = Pin::new(&mut self.buffer).poll_write(context, data);
+
+		if let Poll::Ready(Ok(length)) = result {
+			self.backlog.fetch_add(length as u64, Relaxed);
+		}
+
+		result
+	}
+
+	fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.buffer).poll_flush(context)
+	}
+
+	fn poll_shutdown(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.buffer).poll_shutdown(context)
+	}
+}
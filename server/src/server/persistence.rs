@@ -0,0 +1,43 @@
+use std::io::ErrorKind::NotFound;
+use std::path::PathBuf;
+
+use tokio::fs;
+use tokio::io;
+
+use protocol::{ReadCwData, WriteCwData};
+use protocol::packet::WorldUpdate;
+
+use crate::server::utils::write_atomically;
+use crate::server::Server;
+
+//loot is stored the same way it gets sent to clients: as a compressed [WorldUpdate]
+const LOOT_FILE: &str = "loot.bin";
+
+impl Server {
+	///restores everything written by [Self::save_state]. missing files are not an error, as that is the case on first startup
+	pub async fn load_state(&self) -> io::Result<()> {
+		let data = match fs::read(self.data_file(LOOT_FILE)).await {
+			Ok(data) => data,
+			Err(error) if error.kind() == NotFound => return Ok(()),
+			Err(error) => return Err(error)
+		};
+
+		let world_update = ReadCwData::<WorldUpdate>::read_cw_data(&mut data.as_slice()).await?;
+		*self.loot.write().await = world_update.loot;
+
+		Ok(())
+	}
+
+	pub async fn save_state(&self) -> io::Result<()> {
+		let world_update = WorldUpdate::from(self.loot.read().await.clone());
+
+		let mut data = vec![];
+		data.write_cw_data(&world_update).await?;
+
+		write_atomically(&self.data_file(LOOT_FILE), data).await
+	}
+
+	fn data_file(&self, name: &str) -> PathBuf {
+		self.data_dir.join(name)
+	}
+}
@@ -1,9 +1,10 @@
 mod addon_data;
 
 use std::net::SocketAddr;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 
-use tokio::io::{self, SimplexStream, WriteHalf};
+use tokio::io;
 use tokio::sync::{oneshot, RwLock};
 
 use protocol::packet::{ChatMessageFromServer, FromServer};
@@ -11,7 +12,10 @@ use protocol::packet::common::CreatureId;
 use protocol::utils::io_extensions::WritePacket;
 use protocol::WriteCwData;
 
+use crate::addon::profiles::Profile;
 use crate::server::creature::Creature;
+use crate::server::metrics::PacketCounters;
+use crate::server::outbound::Outbound;
 use crate::server::player::addon_data::AddonData;
 
 #[derive(Debug)]
@@ -19,43 +23,47 @@ pub struct Player {
 	pub address: SocketAddr,
 	pub id: CreatureId,
 	pub character: RwLock<Creature>,
-	pub writer: RwLock<WriteHalf<SimplexStream>>,
-	pub admin: AtomicBool, //todo: move to AddonData
-	pub ac_immune: AtomicBool,
+	pub writer: RwLock<Outbound>,
+	pub backlog: Arc<AtomicU64>, //see [Outbound::backlog]
+	packets_sent: Arc<PacketCounters>, //see [crate::server::metrics::Metrics::packets_sent]
 	pub kick_sender: RwLock<Option<oneshot::Sender<()>>>,
-	pub addon_data: RwLock<AddonData>
+	pub addon_data: RwLock<AddonData>,
+	pub profile: RwLock<Profile> //see [Server::load_profile]
 }
 
 impl Player {
-	pub fn new(address: SocketAddr, id: CreatureId, creature: Creature, writer: WriteHalf<SimplexStream>) -> (Self, oneshot::Receiver<()>) {
+	pub fn new(address: SocketAddr, id: CreatureId, creature: Creature, writer: Outbound, packets_sent: Arc<PacketCounters>) -> (Self, oneshot::Receiver<()>) {
 		let (kick_sender, kick_receiver) = oneshot::channel();
 
 		let instance = Self {
 			address,
 			id,
 			character: RwLock::new(creature),
+			backlog: writer.backlog(),
 			writer: RwLock::new(writer),
-			admin: AtomicBool::default(),
-			ac_immune: AtomicBool::default(),
+			packets_sent,
 			kick_sender: RwLock::new(Some(kick_sender)),
-			addon_data: RwLock::default()
+			addon_data: RwLock::default(),
+			profile: RwLock::default()
 		};
 
 		(instance, kick_receiver)
 	}
 
 	pub async fn send<Packet: FromServer>(&self, packet: &Packet) -> io::Result<()>
-		where WriteHalf<SimplexStream>: WriteCwData<Packet>//todo: specialization could obsolete this
+		where Outbound: WriteCwData<Packet>//todo: specialization could obsolete this
 	{
 		let mut writer = self.writer.write().await;
 		#[expect(trivial_casts, reason = "todo: why is this cast necessary?")]
-		(&mut writer as &mut WriteHalf<SimplexStream>).write_packet(packet).await
+		(&mut writer as &mut Outbound).write_packet(packet).await?;
+		self.packets_sent.count(&Packet::ID, 1);
+		Ok(())
 	}
 
 	///sends a packet to this player and ignores any io errors.
 	///useful when errors are already handled by the reading thread
 	pub async fn send_ignoring<Packet: FromServer>(&self, packet: &Packet)
-		where WriteHalf<SimplexStream>: WriteCwData<Packet>//todo: specialization could obsolete this
+		where Outbound: WriteCwData<Packet>//todo: specialization could obsolete this
 	{
 		#[expect(let_underscore_drop, clippy::let_underscore_must_use, reason="deliberate")]
 		let _ = self.send(packet).await;
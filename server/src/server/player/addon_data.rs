@@ -1,10 +1,16 @@
 use std::time::Instant;
 
 use crate::addon::anti_cheat::PlayerData;
+use crate::addon::{afk, chat_moderation, flood_protection, permissions, reports};
 
 #[derive(Debug, Default)]
 pub struct AddonData {
 	pub team: Option<i32>,
 	pub anti_cheat_data: PlayerData,
-	pub last_attacker: Option<(Instant, String)>
+	pub last_attacker: Option<(Instant, String)>,
+	pub flood_protection: flood_protection::PlayerData,
+	pub permissions: permissions::PlayerData,
+	pub chat_moderation: chat_moderation::PlayerData,
+	pub reports: reports::PlayerData,
+	pub afk: afk::PlayerData
 }
\ No newline at end of file
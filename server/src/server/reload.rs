@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use config::ConfigError;
+use tracing::{error, info};
+
+use crate::server::Server;
+
+impl Server {
+	///loads the config again and swaps in everything that can change at runtime.
+	///nothing changes if the new config is invalid
+	pub async fn reload_config(&self) -> Result<(), ConfigError> {
+		let config = (self.load_config)()?;
+
+		self.motd.write().await.clone_from(&config.motd);
+		self.addons.reload(&config).await;
+
+		info!("config reloaded");
+		Ok(())
+	}
+
+	///reloads the config upon SIGHUP
+	#[cfg(unix)]
+	pub async fn handle_reload_signals(self: Arc<Self>) {
+		use tokio::signal::unix::{signal, SignalKind};
+
+		let mut sighup = signal(SignalKind::hangup()).expect("failed to register SIGHUP handler");
+		while sighup.recv().await.is_some() {
+			_ = self
+				.reload_config()
+				.await
+				.inspect_err(|error| error!(%error, "failed to reload config"));
+		}
+	}
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::{Instant, sleep, sleep_until, timeout};
+
+use crate::server::Server;
+use crate::server::utils::log_error;
+
+///how long players get warned before a shutdown triggered by SIGINT/SIGTERM or ctrl+c in the console
+pub const SIGNAL_DELAY: Duration = Duration::from_secs(10);
+///remaining seconds at which players get reminded of an upcoming shutdown
+const REMINDERS: [u64; 8] = [60, 30, 10, 5, 4, 3, 2, 1];
+///how long sessions get to wind down before the server stops waiting for them
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+	Exit,
+	Restart
+}
+
+impl Outcome {
+	const fn verb(self) -> &'static str {
+		match self {
+			Self::Exit    => "shutting down",
+			Self::Restart => "restarting"
+		}
+	}
+}
+
+impl Server {
+	///announces the shutdown and counts down, after which [Self::serve] disconnects everyone, saves the state and returns.
+	///replaces any previously scheduled shutdown
+	pub async fn schedule_shutdown(self: &Arc<Self>, delay: Duration, reason: Option<String>, outcome: Outcome) {
+		let reason = reason.map_or_else(String::new, |reason| format!(" ({reason})"));
+		let deadline = Instant::now() + delay;
+
+		let server = Arc::clone(self);
+		let countdown = tokio::spawn(async move {
+			if !delay.is_zero() {
+				server.announce(format!("server {} in {}{reason}", outcome.verb(), seconds(delay.as_secs()))).await;
+			}
+
+			for remaining in REMINDERS.into_iter().filter(|remaining| *remaining < delay.as_secs()) {
+				sleep_until(deadline - Duration::from_secs(remaining)).await;
+				server.announce(format!("server {} in {}", outcome.verb(), seconds(remaining))).await;
+			}
+
+			sleep_until(deadline).await;
+			server.stop.send_replace(Some(outcome));
+		});
+
+		if let Some(previous) = self.shutdown_countdown.lock().await.replace(countdown) {
+			previous.abort();
+		}
+	}
+
+	///returns false if there was nothing to cancel
+	pub async fn cancel_shutdown(&self) -> bool {
+		let Some(countdown) = self.shutdown_countdown.lock().await.take()
+			else { return false };
+
+		if countdown.is_finished() {
+			return false; //too late
+		}
+
+		countdown.abort();
+		self.announce("shutdown cancelled").await;
+		true
+	}
+
+	///schedules a shutdown upon SIGINT/SIGTERM. a second signal skips the countdown
+	pub async fn handle_signals(self: Arc<Self>) {
+		termination_signal().await;
+		self.schedule_shutdown(SIGNAL_DELAY, None, Outcome::Exit).await;
+		termination_signal().await;
+		self.schedule_shutdown(Duration::ZERO, None, Outcome::Exit).await;
+	}
+
+	pub(super) async fn wait_for_stop(&self) -> Outcome {
+		let mut receiver = self.stop.subscribe();
+		let outcome = *receiver
+			.wait_for(Option::is_some)
+			.await
+			.expect("the sender is owned by the server itself");
+
+		outcome.expect("checked above")
+	}
+
+	pub(super) async fn shut_down(&self, outcome: Outcome) {
+		self.announce(format!("server is {} now", outcome.verb())).await;
+		//wait a bit to make sure the message arrives before the connections get closed
+		sleep(Duration::from_millis(100)).await;
+
+		let players = self.players.read().await.clone(); //sessions need to acquire this lock to remove themselves
+		for player in players {
+			player
+				.kick_sender
+				.write()
+				.await
+				.take()
+				.map(|sender| sender.send(()));
+		}
+
+		//each session removes its own player
+		let all_disconnected = async {
+			while !self.players.read().await.is_empty() {
+				sleep(Duration::from_millis(10)).await;
+			}
+		};
+		_ = timeout(DISCONNECT_TIMEOUT, all_disconnected).await;
+
+		_ = self
+			.save_state()
+			.await
+			.inspect_err(|err| log_error("save-state", err));
+	}
+}
+
+fn seconds(count: u64) -> String {
+	format!("{count} second{}", if count == 1 { "" } else { "s" })
+}
+
+#[cfg(unix)]
+async fn termination_signal() {
+	use tokio::select;
+	use tokio::signal::unix::{signal, SignalKind};
+
+	let mut sigterm = signal(SignalKind::terminate()).expect("failed to register SIGTERM handler");
+	select! {
+		_ = tokio::signal::ctrl_c() => {},
+		_ = sigterm.recv() => {}
+	}
+}
+
+#[cfg(not(unix))]
+async fn termination_signal() {
+	_ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,53 @@
+use tokio::time::Instant;
+
+///allows bursts of up to `capacity` actions, refilling at a steady rate afterwards
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+	capacity: f64,
+	refill_rate: f64, //tokens per second
+	tokens: f64,
+	last_refill: Instant
+}
+
+impl TokenBucket {
+	pub fn new(capacity: f64, refill_rate: f64) -> Self {
+		Self {
+			capacity,
+			refill_rate,
+			tokens: capacity,
+			last_refill: Instant::now()
+		}
+	}
+
+	///returns false if the bucket is empty
+	pub fn try_take(&mut self) -> bool {
+		self.refill();
+
+		if self.tokens < 1.0 {
+			return false;
+		}
+
+		self.tokens -= 1.0;
+		true
+	}
+
+	///like [Self::try_take], without taking anything
+	pub fn is_empty(&mut self) -> bool {
+		self.refill();
+		self.tokens < 1.0
+	}
+
+	///a full bucket is indistinguishable from a fresh one, so it can be discarded
+	pub fn is_full(&mut self) -> bool {
+		self.refill();
+		self.tokens >= self.capacity
+	}
+
+	fn refill(&mut self) {
+		let now = Instant::now();
+		let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+
+		self.tokens = elapsed.mul_add(self.refill_rate, self.tokens).min(self.capacity);
+		self.last_refill = now;
+	}
+}
@@ -1,12 +1,11 @@
-use std::fs;
 use std::error::Error;
-use std::mem::transmute;
-use std::time::{UNIX_EPOCH, SystemTime};
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use colour::{white_ln, red_ln};
+use tokio::{fs, io};
 use tokio::time::sleep;
+use tracing::{error, info, warn};
 
 use protocol::packet::Hit;
 use protocol::packet::world_update::{Particle, Sound};
@@ -22,8 +21,9 @@ use protocol::packet::world_update::Kill;
 
 use crate::server::player::Player;
 use crate::addon::kill_feed;
+use crate::addon::permissions::{Issuer, ADMIN_NOTIFICATIONS};
+use crate::addon::rcon::Event;
 use crate::server::Server;
-use crate::SERVER;
 
 use super::send_existing_creatures;
 use super::creature::Creature;
@@ -32,26 +32,44 @@ impl Server {
 	pub async fn announce(&self, text: impl Into<String>) {
 		let text = text.into();//todo: is there a way to prevent this boilerplate?
 
-		white_ln!("{}", text);
+		info!("{text}");
 		self.addons.discord_integration.post(&format!("*{text}*"), false).await;
+		self.addons.rcon.publish(Event::Announcement(text.clone()));
 		self.broadcast(&ChatMessageFromServer {
 			source: CreatureId(0),
 			text
 		}, None).await;
 	}
 
-	pub async fn kick(&self, player: &Player, reason: impl Into<String>) {
-		self.announce(format!("kicked {} because {}", player.character.read().await.name, reason.into())).await;
+	///posts `text` to the discord admin channel and notifies everyone online permitted to receive it
+	pub async fn notify_admins(&self, text: impl Into<String>) {
+		let text = text.into();
+
+		info!(target: "admin", "{text}");
+		self.addons.discord_integration.post(&text, true).await;
+
+		let players = self.players.read().await.clone();
+		for player in players {
+			if self.addons.permissions.allows(Issuer::Player(&player), ADMIN_NOTIFICATIONS).await {
+				player.notify(text.clone()).await;
+			}
+		}
+	}
+
+	///returns false if the player was getting kicked already
+	pub async fn kick(&self, player: &Player, reason: impl Into<String>) -> bool {
+		let Some(kick_sender) = player.kick_sender.write().await.take()
+			else { return false };
+
+		let reason = reason.into();
+		warn!(player = %player.address, id = player.id.0, reason, "kick");
+		self.announce(format!("kicked {} because {}", player.character.read().await.name, reason)).await;
 		//wait a bit to make sure the message arrives at the player about to be kicked
 		sleep(Duration::from_millis(100)).await;
 
-		player
-			.kick_sender
-			.write()
-			.await
-			.take()
-			.map(|sender| sender.send(()));
+		_ = kick_sender.send(());
 		//remove_player will be called by the reading task
+		true
 	}
 
 	pub async fn teleport(&self, player: &Player, destination: Point3<i64>) {
@@ -64,6 +82,11 @@ impl Server {
 		};
 		player.send_ignoring(&server_creature).await;
 		sleep(Duration::from_millis(100)).await;
+		self.reload_creatures(player).await;
+	}
+
+	///makes the client forget all creatures, then sends everything that still exists
+	pub async fn reload_creatures(&self, player: &Player) {
 		player.send_ignoring(&ServerTick).await;
 		player.send_ignoring(&ServerTick).await;
 		send_existing_creatures(self, player).await;
@@ -79,7 +102,8 @@ impl Server {
 			.map(Arc::clone)
 	}
 
-	pub async fn apply_dot(&self, source_character: &Creature, target: Arc<Player>, ticks: i32, delay: u64, damage: f32, sound_kind: sound::Kind, particles: Option<Vec<Particle>>) {
+	#[expect(clippy::too_many_arguments, reason = "each parameter is a separate aspect of the effect, callers pass a different combination every time")]
+	pub async fn apply_dot(self: &Arc<Self>, source_character: &Creature, target: Arc<Player>, ticks: i32, delay: u64, damage: f32, sound_kind: sound::Kind, particles: Option<Vec<Particle>>) {
 		let target_character_guard = target.character.read().await;
 
 		let mut hit = Hit {
@@ -94,7 +118,7 @@ impl Server {
 			kind: Normal,
 			flash: true,
 		};
-		self.addons.balancing.adjust_hit(&mut hit, source_character, &target_character_guard);
+		self.addons.balancing.adjust_hit(&mut hit, source_character, &target_character_guard).await;
 		drop(target_character_guard);
 
 		let mut wu_fx = WorldUpdate {
@@ -105,6 +129,7 @@ impl Server {
 		let mut wu_hit = WorldUpdate::from(hit);
 
 		let attacker_name = source_character.name.clone();
+		let server = Arc::clone(self);
 		tokio::spawn(async move {
 			let mut nth = 0;
 			loop {
@@ -126,7 +151,7 @@ impl Server {
 				if target.send(&wu_hit).await.is_err() {
 					break; //disconnects are handled in the reading task
 				}
-				SERVER.broadcast(&wu_fx, None).await;
+				server.broadcast(&wu_fx, None).await;
 
 
 				if nth == ticks {
@@ -140,25 +165,65 @@ impl Server {
 }
 
 pub fn log_error(description: &str, err: impl Error) {
-	red_ln!("error at {description}: {err}");
+	error!(context = description, "{err}");
+}
+
+///writing to a temporary file first ensures that a crash mid-write doesn't corrupt the previous version
+pub async fn write_atomically(path: &Path, data: impl AsRef<[u8]>) -> io::Result<()> {
+	let temporary = path.with_extension("tmp");
+	fs::write(&temporary, data).await?;
+	fs::rename(temporary, path).await
+}
 
-	let filename = SystemTime::now()
+///seconds since the unix epoch, for timestamps that need to survive a restart
+pub fn unix_now() -> u64 {
+	SystemTime::now()
 		.duration_since(UNIX_EPOCH)
 		.expect("time went backwards")
-		.as_millis()
-		.to_string();
+		.as_secs()
+}
 
-	let _result = fs::create_dir_all(format!("logs/{description}")); //if it already exists thats fine too
+const DURATION_UNITS: [(&str, u64); 5] = [("w", 7 * 24 * 60 * 60), ("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)];
 
-	fs::write(format!("logs/{description}/{filename}.log"), err.to_string())
-		.expect("failed writing log file");
+///anything longer might as well be permanent, and could overflow once added to the current time
+const MAX_DURATION: u64 = 520 * 7 * 24 * 60 * 60;
 
+///parses durations like `90s`, `30m`, `12h`, `7d` or `2w`. zero is not a valid duration, neither is anything beyond 520w
+pub fn parse_duration(text: &str) -> Option<Duration> {
+	let unit_start = text.find(|character: char| !character.is_ascii_digit())?;
+	let (amount, unit) = text.split_at(unit_start);
+	let (_unit, factor) = DURATION_UNITS.into_iter().find(|(name, _factor)| *name == unit)?;
+
+	amount
+		.parse::<u64>().ok()?
+		.checked_mul(factor)
+		.filter(|seconds| (1..=MAX_DURATION).contains(seconds))
+		.map(Duration::from_secs)
+}
+
+///the 2 largest units, e.g. `2d 5h` or `10m`
+pub fn format_duration(duration: Duration) -> String {
+	let mut remaining = duration.as_secs();
+	let parts = DURATION_UNITS
+		.into_iter()
+		.filter_map(|(unit, factor)| {
+			let amount = remaining / factor;
+			remaining %= factor;
+			(amount > 0).then(|| format!("{amount}{unit}"))
+		})
+		.take(2)
+		.collect::<Vec<_>>();
+
+	if parts.is_empty() {
+		"0s".into()
+	} else {
+		parts.join(" ")
+	}
 }
 
-///terrible hack due to my inexperience. unsafe as fuck
-pub fn extend_lifetime<T>(it: &T) -> &'static T {
-	#[expect(clippy::undocumented_unsafe_blocks, reason = "TODO")]
-	unsafe { transmute(it) } //TODO: figure out scoped tasks
+///prevents guessing secrets byte by byte through response timings
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).fold(0, |difference, (x, y)| difference | (x ^ y)) == 0
 }
 
 pub async fn give_xp(player: &Player, experience: i32) {
@@ -0,0 +1,26 @@
+mod harness;
+mod pvp;
+mod kill_feed;
+mod commands;
+mod anti_cheat;
+mod config;
+mod shutdown;
+mod reload;
+mod join_queue;
+mod connection_limit;
+mod flood_protection;
+mod metrics;
+mod admin_api;
+mod console;
+mod rcon;
+mod permissions;
+mod bans;
+mod chat_moderation;
+mod profiles;
+mod names;
+mod whitelist;
+mod audit;
+mod reports;
+mod voting;
+mod afk;
+mod logging;
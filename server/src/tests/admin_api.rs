@@ -0,0 +1,54 @@
+use crate::tests::harness::{free_port, http, CONFIG, TestServer};
+
+async fn start() -> (TestServer, u16) {
+	let port = free_port();
+	let test_server = TestServer::with_config(&format!("listforgeapi_port = {port}\nadmin_api_token = \"secret\"\n{CONFIG}")).await;
+	(test_server, port)
+}
+
+#[tokio::test]
+async fn requests_without_the_token_are_rejected() {
+	let (_test_server, port) = start().await;
+
+	assert_eq!(http(port, "GET", "/api/admin/players", None, "").await.0, 401);
+	assert_eq!(http(port, "GET", "/api/admin/players", Some("guess"), "").await.0, 401);
+	assert_eq!(http(port, "GET", "/api/info", None, "").await.0, 200);
+}
+
+#[tokio::test]
+async fn players_can_be_listed_and_kicked() {
+	let (test_server, port) = start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	let (status, body) = http(port, "GET", "/api/admin/players", Some("secret"), "").await;
+	assert_eq!(status, 200);
+	assert!(body.contains(&format!("\"id\":{},\"name\":\"alice\",\"ip\":\"127.0.0.1\"", alice.id.0)), "{body}");
+
+	let path = format!("/api/admin/players/{}/kick", alice.id.0);
+	assert_eq!(http(port, "POST", &path, Some("secret"), r#"{"reason":"testing"}"#).await.0, 204);
+	alice.expect_chat(|text| text == "kicked alice because testing").await;
+	alice.expect_disconnect().await;
+
+	assert_eq!(http(port, "POST", "/api/admin/players/12345/kick", Some("secret"), "{}").await.0, 404);
+
+	//recorded after the fact, along with how it went
+	let (_status, body) = http(port, "POST", "/api/admin/command", Some("secret"), r#"{"command":"audit"}"#).await;
+	assert!(body.contains(&format!("admin api: kick alice (player #{}) (testing) -> ok", alice.id.0)), "{body}");
+	assert!(body.contains("admin api: kick player #12345 () -> failed (player not found)"), "{body}");
+}
+
+#[tokio::test]
+async fn commands_run_with_admin_rights() {
+	let (test_server, port) = start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	let (status, body) = http(port, "POST", "/api/admin/command", Some("secret"), r#"{"command":"kick alice"}"#).await;
+	assert_eq!((status, body.as_str()), (200, r#"{"response":null}"#));
+	alice.expect_disconnect().await;
+
+	let (status, body) = http(port, "POST", "/api/admin/command", Some("secret"), r#"{"command":"nonsense"}"#).await;
+	assert_eq!((status, body.as_str()), (400, r#"{"error":"unknown command (type /help for a list)"}"#));
+
+	let (_status, body) = http(port, "POST", "/api/admin/command", Some("secret"), r#"{"command":"audit kick alice"}"#).await;
+	assert!(body.contains("admin api: kick alice -> ok"), "{body}");
+}
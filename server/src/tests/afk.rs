@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use protocol::nalgebra::Vector3;
+use protocol::packet::CreatureUpdate;
+
+use crate::tests::harness::{CONFIG, Received, TestServer};
+
+#[tokio::test]
+async fn idle_players_get_marked_warned_and_kicked_unless_exempt() {
+	let test_server = TestServer::with_config(&format!("{CONFIG}\n[afk]\nmark_after = 1\nwarn_after = 2\nkick_after = 3")).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+	bob.chat("/login admin").await; //grants everything, including afk.exempt
+
+	alice.expect_chat(|text| text == "you are now marked as afk").await;
+	bob.chat("/who").await;
+	bob.expect_chat(|text| text.contains("alice (afk)")).await;
+
+	alice.send(&CreatureUpdate {
+		id: alice.id,
+		acceleration: Some(Vector3::new(10.0, 0.0, 0.0)),
+		..Default::default()
+	}).await;
+	alice.expect_chat(|text| text == "you are no longer marked as afk").await;
+
+	alice.expect_chat(|text| text == "you will be kicked for being afk in 1s, unless you move").await;
+	alice.expect_disconnect().await;
+
+	//long past the kick threshold
+	bob.expect_chat(|text| text == "you are now marked as afk").await;
+	sleep(Duration::from_secs(3)).await;
+	bob.expect_none(|received| matches!(received, Received::ChatMessage(message) if message.text.starts_with("you will be kicked"))).await;
+	bob.chat("/who").await;
+	bob.expect_chat(|text| text.contains("bob")).await;
+}
@@ -0,0 +1,36 @@
+use protocol::packet::CreatureUpdate;
+
+use crate::tests::harness::{Received, TestServer};
+
+#[tokio::test]
+async fn cheaters_get_kicked() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.send(&CreatureUpdate {
+		id: alice.id,
+		level: Some(9999),
+		..Default::default()
+	}).await;
+
+	bob.expect_chat(|text| text == "kicked alice because level was 9999, allowed was within 1..=500").await;
+	alice.expect_disconnect().await;
+}
+
+#[tokio::test]
+async fn immune_players_dont_get_kicked() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login immune").await;
+	alice.expect_chat(|text| text == "login successful").await;
+
+	alice.send(&CreatureUpdate {
+		id: alice.id,
+		level: Some(9999),
+		..Default::default()
+	}).await;
+
+	alice.expect_none(|received| matches!(received, Received::ChatMessage(message) if message.text.starts_with("kicked"))).await;
+}
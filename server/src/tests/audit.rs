@@ -0,0 +1,51 @@
+use crate::tests::harness::{CONFIG, TestServer, data_dir};
+
+#[tokio::test]
+async fn privileged_commands_are_recorded() {
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir("audit").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	bob.chat("/kick alice").await;
+	bob.expect_chat(|text| text == "no permission").await;
+	bob.chat("/who").await; //available to everyone, so not worth recording
+	alice.chat("/kick bob griefing").await;
+	bob.expect_disconnect().await;
+
+	alice.chat("/audit").await;
+	let entries = alice.expect_chat(|text| text.contains("kick bob griefing")).await;
+	let lines = entries.lines().collect::<Vec<_>>();
+	assert_eq!(lines.len(), 2, "{entries}");
+	assert!(lines[0].contains(" ago, bob (player #") && lines[0].ends_with("): kick alice -> failed (no permission)"), "{entries}");
+	assert!(lines[1].contains(" ago, alice (player #") && lines[1].ends_with("): kick bob griefing -> ok"), "{entries}");
+	drop(test_server);
+
+	let test_server = TestServer::with_config(&config).await;
+	let mut carol = test_server.connect("carol").await;
+	carol.chat("/login admin").await;
+	carol.chat("/audit 1 griefing").await;
+	carol.expect_chat(|text| text.contains(", alice (player #") && text.ends_with("): kick bob griefing -> ok")).await;
+}
+
+#[tokio::test]
+async fn game_changing_commands_are_recorded_even_if_everyone_may_use_them() {
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir("audit-default-commands").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	let _bob = test_server.connect("bob").await;
+
+	alice.chat("/tp bob").await;
+	alice.chat("/give weapon.sword").await;
+	alice.chat("/who").await; //changes nothing, so not worth recording
+	alice.expect_chat(|text| text.contains(" alice")).await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/audit").await;
+	let entries = alice.expect_chat(|text| text.contains("give weapon.sword")).await;
+	let lines = entries.lines().collect::<Vec<_>>();
+	assert_eq!(lines.len(), 2, "{entries}");
+	assert!(lines[0].ends_with("): tp bob -> ok"), "{entries}");
+	assert!(lines[1].ends_with("): give weapon.sword -> ok"), "{entries}");
+}
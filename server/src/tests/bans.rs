@@ -0,0 +1,71 @@
+use crate::tests::harness::{CONFIG, Received, TestServer, data_dir};
+
+#[tokio::test]
+async fn bans_persist_and_are_enforced_until_lifted() {
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir("bans").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/ban bob griefing").await;
+	bob.expect_chat(|text| text == "kicked bob because banned (griefing)").await;
+	bob.expect_disconnect().await;
+	drop(test_server);
+
+	let test_server = TestServer::with_config(&config).await;
+	let mut bob = test_server.connect_without_joining("bob").await;
+	bob.expect_chat(|text| text == "you are banned: griefing").await;
+	bob.expect_disconnect().await;
+
+	let mut alice = test_server.connect("alice").await;
+	alice.chat("/login admin").await;
+	alice.chat("/banlist").await;
+	let entry = format!("bob by alice (player #{}) (griefing), permanent", alice.id.0);
+	alice.expect_chat(|text| text == entry).await;
+	alice.chat("/unban bob").await;
+	alice.expect_chat(|text| text == "unbanned bob").await;
+	test_server.connect("bob").await;
+}
+
+#[tokio::test]
+async fn tempbans_show_the_remaining_time_and_ip_bans_cover_ranges() {
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir("tempbans").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/tempban soon carol").await;
+	alice.expect_chat(|text| text.starts_with("invalid duration")).await;
+	alice.chat("/tempban 18446744073709551615s carol").await;
+	alice.expect_chat(|text| text.starts_with("invalid duration")).await;
+	alice.chat("/tempban 2h carol").await;
+	alice.expect_chat(|text| text == "banned carol").await;
+
+	let mut carol = test_server.connect_without_joining("carol").await;
+	carol.expect_chat(|text| ["you are banned (2h left)", "you are banned (1h 59m left)"].contains(&text)).await;
+	carol.expect_disconnect().await;
+
+	alice.chat("/ipban 10.0.0.0/8").await;
+	alice.expect_chat(|text| text == "banned 10.0.0.0/8").await;
+	test_server.connect("dave").await; //connects from 127.0.0.1
+}
+
+#[tokio::test]
+async fn partial_names_never_hit_someone_else() {
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir("partial-names").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bobby").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/ipban bob").await;
+	alice.expect_chat(|text| text == "target not found").await;
+	alice.chat("/ban bob").await;
+	alice.expect_chat(|text| text == "banned bob").await; //an offline player of that name
+	bob.expect_none(|received| matches!(received, Received::ChatMessage(message) if message.text.starts_with("kicked"))).await;
+
+	alice.chat(&format!("/ban #{}", bob.id.0)).await;
+	alice.expect_chat(|text| text == "banned bobby").await;
+	bob.expect_disconnect().await;
+}
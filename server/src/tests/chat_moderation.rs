@@ -0,0 +1,75 @@
+use protocol::packet::CreatureUpdate;
+
+use crate::tests::harness::{CONFIG, TestServer};
+
+#[tokio::test]
+async fn muted_players_cannot_chat_until_unmuted() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/mute bob 10m spamming").await;
+	alice.expect_chat(|text| text == "muted bob").await;
+	bob.expect_chat(|text| text.starts_with("you are muted: spamming (")).await;
+
+	bob.chat("hello").await;
+	bob.expect_chat(|text| text.starts_with("you are muted: spamming (")).await;
+	alice.expect_chat(|text| text == "blocked message from bob (muted): hello").await;
+
+	alice.chat("/unmute bob").await;
+	bob.expect_chat(|text| text == "you are no longer muted").await;
+	bob.chat("hello again").await;
+	alice.expect_chat(|text| text == "hello again").await;
+}
+
+#[tokio::test]
+async fn mutes_stick_to_players_changing_names() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/mute bob 18446744073709551615s").await; //too long to be a duration, so it's part of the reason
+	alice.expect_chat(|text| text == "muted bob").await;
+	bob.expect_chat(|text| text == "you are muted: 18446744073709551615s").await;
+
+	bob.send(&CreatureUpdate {
+		id: bob.id,
+		name: Some("robert".into()),
+		..Default::default()
+	}).await;
+	bob.chat("hello").await;
+	bob.expect_chat(|text| text == "you are muted: 18446744073709551615s").await;
+	alice.expect_chat(|text| text == "blocked message from robert (muted): hello").await;
+
+	alice.chat("/unmute robert").await;
+	bob.expect_chat(|text| text == "you are no longer muted").await;
+	bob.chat("hello again").await;
+	alice.expect_chat(|text| text == "hello again").await;
+}
+
+#[tokio::test]
+async fn messages_are_filtered() {
+	let chat = "[chat]\nmax_length = 30\n[chat.words]\nidiot = \"replace\"\ngrief = \"block\"";
+	let test_server = TestServer::with_config(&format!("{CONFIG}\n{chat}\n[packet_limits]\nchat = {{ burst = 20, per_second = 1.0 }}")).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	bob.chat("you IDIOT").await;
+	alice.expect_chat(|text| text == "you *****").await;
+	bob.chat("WHY IS NOBODY HERE").await;
+	alice.expect_chat(|text| text == "why is nobody here").await;
+
+	bob.chat("lets grief alice").await;
+	bob.expect_chat(|text| text == "your message contains a word that isn't allowed here").await;
+	bob.chat("free stuff at example.com/free").await;
+	bob.expect_chat(|text| text == "links are not allowed here").await;
+	bob.chat("this message is way too long to be sent").await;
+	bob.expect_chat(|text| text == "your message is too long (39/30 characters)").await;
+	bob.chat("why is nobody here").await;
+	bob.expect_chat(|text| text == "please don't repeat yourself").await;
+
+	bob.chat("fine").await;
+	alice.expect_chat(|text| text == "fine").await;
+}
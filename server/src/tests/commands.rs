@@ -0,0 +1,57 @@
+use crate::tests::harness::{Received, TestServer};
+
+#[tokio::test]
+async fn who_lists_everyone() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let _bob = test_server.connect("bob").await;
+
+	alice.chat("/who").await;
+	let response = alice.expect_chat(|text| text.starts_with('#')).await;
+	assert!(response.contains("alice"));
+	assert!(response.contains("bob"));
+}
+
+#[tokio::test]
+async fn commands_are_not_echoed() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/who").await;
+	bob.expect_none(|received| matches!(received, Received::ChatMessage(message) if message.text.contains("who"))).await;
+
+	alice.chat("hello").await;
+	bob.expect_chat(|text| text == "hello").await;
+}
+
+#[tokio::test]
+async fn unknown_command() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/nonexistent").await;
+	alice.expect_chat(|text| text.starts_with("unknown command")).await;
+}
+
+#[tokio::test]
+async fn admin_commands_require_login() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/kick bob").await;
+	alice.expect_chat(|text| text == "no permission").await;
+
+	alice.chat("/login wrong").await;
+	alice.expect_chat(|text| text == "wrong password").await;
+
+	alice.chat("/login admin").await;
+	alice.expect_chat(|text| text == "login successful").await;
+
+	alice.chat("/kick bob being bob").await;
+	alice.expect_chat(|text| text == "kicked bob because being bob").await;
+	bob.expect_disconnect().await;
+	alice.expect_chat(|text| text == "[-] bob").await;
+	assert_eq!(test_server.server.players.read().await.len(), 1);
+}
@@ -0,0 +1,42 @@
+use crate::server::config::{EXAMPLE, ServerConfig};
+use crate::tests::harness::parse_config;
+
+#[test]
+fn example_matches_defaults() {
+	assert_eq!(parse_config(EXAMPLE).unwrap(), ServerConfig::default());
+	assert_eq!(parse_config("").unwrap(), ServerConfig::default());
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+	for (toml, message) in [
+		("port = 0"                                 , "port must be within 1..=65535"),
+		("bind = []"                                , "bind must contain at least 1 address"),
+		("slots = 0"                                , "slots must be at least 1"),
+		("max_connections_per_ip = 0"               , "max_connections_per_ip must be at least 1"),
+		("listforgeapi_port = 12345"                , "listforgeapi_port must not be the game port (12345)"),
+		("discord_bot_token = \"x\""                , "discord_public_channel_id is required when discord_bot_token is set"),
+		("[models]\n\"missing.vox\" = [0, 0, 0]"    , "model file 'missing.vox' does not exist"),
+		("[balance.damage]\nfire_spirit = 1.0"      , "balance.damage must contain 'global'"),
+		("[balance]\nignite_combo = 0"              , "balance.ignite_combo must be at least 1"),
+		("[packet_limits]\nchat = { burst = 0, per_second = 1.0 }", "packet_limits.chat.burst must be at least 1"),
+		("[log]\nrotation = \"weekly\""             , "'weekly' is neither never/minutely/hourly/daily nor a size like 10MB for key `log.rotation`"),
+		("[log]\nrotation = \"0MB\""                , "log.rotation size must be at least 1 byte"),
+		("[log]\nrotation = \"99999999999GB\""      , "'99999999999GB' is neither never/minutely/hourly/daily nor a size like 10MB for key `log.rotation`"),
+		("rcon_port = 12347"                        , "rcon_password is required when rcon_port is set"),
+		("[roles.Admin]"                            , "role name 'Admin' must be lowercase without whitespace"),
+		("[chat.words]\n\"bad word\" = \"block\"" , "chat.words key 'bad word' must be a single lowercase word"),
+		("[names]\nreserved = [\" \"]"              , "names.reserved must not contain empty entries"),
+		("[voting]\nquorum = 1.5"                   , "voting.quorum must be within 0.0..=1.0"),
+		("[afk]\nwarn_after = 900"                  , "afk.warn_after must be less than afk.kick_after"),
+		("admin_password = \"x\""                   , "admin_password has been replaced by roles, remove it and set `password` in [roles.admin] instead"),
+		("ac_immune_password = \"x\""               , "ac_immune_password has been replaced by roles, remove it and set `password` in [roles.immune] instead")
+	] {
+		assert_eq!(parse_config(toml).unwrap_err().to_string(), message);
+	}
+}
+
+#[test]
+fn unknown_keys_are_rejected() {
+	assert!(parse_config("sead = 1").is_err());
+}
@@ -0,0 +1,27 @@
+use crate::tests::harness::{expect_dropped, CONFIG, TestServer};
+
+#[tokio::test]
+async fn stalled_handshakes_get_dropped() {
+	let test_server = TestServer::with_config(&format!("handshake_timeout = 1\n{CONFIG}")).await;
+
+	expect_dropped(test_server.connect_raw().await).await;
+	let _alice = test_server.connect("alice").await;
+}
+
+#[tokio::test]
+async fn excess_connections_per_ip_get_dropped() {
+	let test_server = TestServer::with_config(&format!("max_connections_per_ip = 2\n{CONFIG}")).await;
+	let _alice = test_server.connect("alice").await;
+	let _idle = test_server.connect_raw().await;
+
+	expect_dropped(test_server.connect_raw().await).await;
+}
+
+#[tokio::test]
+async fn connection_bursts_get_dropped() {
+	let test_server = TestServer::with_config(&format!("connections_per_minute_per_ip = 2\n{CONFIG}")).await;
+	drop(test_server.connect("alice").await);
+	drop(test_server.connect_raw().await);
+
+	expect_dropped(test_server.connect_raw().await).await;
+}
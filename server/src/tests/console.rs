@@ -0,0 +1,29 @@
+use crate::addon::permissions::Issuer;
+use crate::console::execute;
+use crate::tests::harness::TestServer;
+
+#[tokio::test]
+async fn say_announces() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	assert_eq!(execute(&test_server.server, Issuer::Console, "say hello everyone").await, None);
+	alice.expect_chat(|text| text == "hello everyone").await;
+
+	assert_eq!(execute(&test_server.server, Issuer::Console, "say").await.as_deref(), Some("usage: say <text>"));
+}
+
+#[tokio::test]
+async fn commands_run_with_admin_rights() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	let response = execute(&test_server.server, Issuer::Console, "who").await.unwrap();
+	assert!(response.contains("alice"), "{response}");
+
+	assert_eq!(execute(&test_server.server, Issuer::Console, "/kick alice").await, None);
+	alice.expect_disconnect().await;
+
+	let response = execute(&test_server.server, Issuer::Console, "sayonara").await.unwrap();
+	assert!(response.starts_with("unknown command"), "{response}");
+}
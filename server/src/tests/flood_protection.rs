@@ -0,0 +1,27 @@
+use std::sync::atomic::Ordering::Relaxed;
+
+use crate::tests::harness::{CONFIG, Received, TestServer};
+
+#[tokio::test]
+async fn chat_floods_escalate_to_a_kick() {
+	let test_server = TestServer::with_config(&format!("{CONFIG}\n[packet_limits]\nkick_after = 2\nchat = {{ burst = 2, per_second = 0.01 }}")).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	for text in ["1", "2", "3", "4", "5"] {
+		alice.chat(text).await;
+	}
+
+	alice.expect_chat(|text| text == "you are sending chat messages too quickly, excess ones will be ignored").await;
+	bob.expect(|received| match received {
+		Received::ChatMessage(message) => {
+			assert!(!["3", "4", "5"].contains(&message.text.as_str()), "dropped message was broadcast");
+			(message.text == "kicked alice because flooding (chat)").then_some(())
+		},
+		_ => None
+	}).await;
+	alice.expect_disconnect().await;
+
+	let stats = &test_server.server.addons.flood_protection.statistics;
+	assert_eq!(stats.kicks.load(Relaxed), 1);
+}
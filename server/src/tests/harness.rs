@@ -0,0 +1,394 @@
+use std::{env, fs, process};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::Duration;
+
+use config::{Config, ConfigError, File, FileFormat};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+use protocol::{Packet, WriteCwData};
+use protocol::nalgebra::{Point3, Vector3};
+use protocol::packet::*;
+use protocol::packet::common::{CreatureId, EulerAngles, Hitbox, Item, Race};
+use protocol::packet::creature_update::{Affiliation, Animation, Appearance, Occupation, Specialization};
+use protocol::utils::flagset::FlagSet;
+use protocol::utils::io_extensions::{ReadArbitrary, ReadPacket, WritePacket};
+
+use crate::server::config::{ConfigLoader, ServerConfig};
+use crate::server::creature::Creature;
+use crate::server::shutdown::Outcome;
+use crate::server::Server;
+
+pub const PATIENCE: Duration = Duration::from_secs(5);
+
+pub const CONFIG: &str = r#"
+seed = 1
+motd = "welcome to the test server"
+
+[roles.admin]
+password = "admin"
+permissions = ["*"]
+
+[roles.immune]
+password = "immune"
+permissions = ["anti_cheat.bypass"]
+
+[warps]
+spawn = [0, 0, 0]
+"#;
+
+static NEXT_SERVER: AtomicUsize = AtomicUsize::new(0);
+
+///a fresh directory for tests that need to persist state
+pub fn data_dir(test_name: &str) -> PathBuf {
+	let path = env::temp_dir().join(format!("berld-{test_name}-{}", process::id()));
+	_ = fs::remove_dir_all(&path);
+	fs::create_dir_all(&path).unwrap();
+	path
+}
+
+pub fn parse_config(toml: &str) -> Result<ServerConfig, ConfigError> {
+	Config::builder()
+		.add_source(File::from_str(toml, FileFormat::Toml))
+		.build()
+		.and_then(ServerConfig::load)
+}
+
+///a [Server] listening on a random local port, torn down on drop
+pub struct TestServer {
+	pub server: Arc<Server>,
+	address: SocketAddr,
+	join_handle: JoinHandle<Outcome>
+}
+
+impl TestServer {
+	pub async fn start() -> Self {
+		Self::with_config(CONFIG).await
+	}
+
+	pub async fn with_config(toml: &str) -> Self {
+		let toml = toml.to_owned();
+		Self::with_loader(Box::new(move || parse_config(&toml))).await
+	}
+
+	pub async fn with_loader(load_config: ConfigLoader) -> Self {
+		let mut config = load_config().expect("invalid test config");
+		//keeps profiles and the like out of the source tree
+		if config.data_dir == ServerConfig::default().data_dir {
+			config.data_dir = data_dir(&format!("server-{}", NEXT_SERVER.fetch_add(1, Relaxed)));
+		}
+		let server = Arc::new(Server::new(&config, load_config));
+
+		let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+		let address = listener.local_addr().unwrap();
+		let serving = Arc::clone(&server);
+		let join_handle = tokio::spawn(serving.serve(vec![listener]));
+
+		Self { server, address, join_handle }
+	}
+
+	///connects a client and waits until the server has added it to the player list
+	pub async fn connect(&self, name: &str) -> TestClient {
+		let mut client = TestClient::connect(self.address, name).await;
+		client.expect(|received| matches!(received, Received::MapSeed(_)).then_some(())).await;
+		client
+	}
+
+	///connects a client without waiting for it to join, e.g. because it ends up in the queue
+	pub async fn connect_without_joining(&self, name: &str) -> TestClient {
+		TestClient::connect(self.address, name).await
+	}
+
+	///a bare tcp connection that hasn't even started the handshake
+	pub async fn connect_raw(&self) -> TcpStream {
+		TcpStream::connect(self.address).await.unwrap()
+	}
+
+	///asserts that the server turns down the next connection attempt right away
+	pub async fn expect_rejection(&self) {
+		let stream = TcpStream::connect(self.address).await.unwrap();
+		let (reader, mut writer) = stream.into_split();
+		let mut reader = BufReader::new(reader);
+
+		writer.write_packet(&ProtocolVersion(3)).await.unwrap();
+		assert_eq!(reader.read_id().await.unwrap(), ConnectionRejection::ID);
+	}
+
+	///waits for [Server::serve] to return
+	pub async fn stopped(&mut self) -> Outcome {
+		timeout(PATIENCE, &mut self.join_handle)
+			.await
+			.expect("server didn't stop in time")
+			.expect("server panicked")
+	}
+}
+
+impl Drop for TestServer {
+	fn drop(&mut self) {
+		self.join_handle.abort();
+	}
+}
+
+///a port that was free a moment ago
+pub fn free_port() -> u16 {
+	std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+		.and_then(|listener| listener.local_addr())
+		.unwrap()
+		.port()
+}
+
+///connects to a port that addons bind in the background, retrying until they start listening
+pub async fn connect_when_listening(port: u16) -> TcpStream {
+	timeout(PATIENCE, async {
+		loop {
+			if let Ok(stream) = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).await {
+				return stream;
+			}
+			sleep(Duration::from_millis(10)).await;
+		}
+	}).await.expect("nothing ever started listening")
+}
+
+///sends a minimal http/1.1 request, retrying until the server starts listening. returns status code and body
+pub async fn http(port: u16, method: &str, path: &str, token: Option<&str>, body: &str) -> (u16, String) {
+	let mut stream = connect_when_listening(port).await;
+
+	let authorization = token.map(|token| format!("Authorization: Bearer {token}\r\n")).unwrap_or_default();
+	let request = format!(
+		"{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{authorization}\r\n{body}",
+		body.len()
+	);
+	stream.write_all(request.as_bytes()).await.unwrap();
+
+	let mut response = String::new();
+	timeout(PATIENCE, stream.read_to_string(&mut response)).await.unwrap().unwrap();
+
+	let (head, body) = response.split_once("\r\n\r\n").expect("malformed http response");
+	let status = head.split(' ').nth(1).and_then(|code| code.parse().ok()).expect("malformed status line");
+	(status, body.to_owned())
+}
+
+///asserts that the server closes `stream` without sending anything
+pub async fn expect_dropped(mut stream: TcpStream) {
+	let read = timeout(PATIENCE, stream.read(&mut [0_u8; 1]))
+		.await
+		.expect("connection was never dropped");
+
+	assert!(read.is_err() || read.is_ok_and(|length| length == 0), "server sent data");
+}
+
+#[allow(dead_code, reason = "not every kind of packet is inspected by tests")]
+#[derive(Debug)]
+pub enum Received {
+	CreatureUpdate(Box<CreatureUpdate>),
+	ServerTick,
+	AirshipTraffic(AirshipTraffic),
+	WorldUpdate(Box<WorldUpdate>),
+	IngameDatetime(IngameDatetime),
+	ChatMessage(ChatMessageFromServer),
+	MapSeed(MapSeed)
+}
+
+///a scripted fake client. every packet it receives is queued up until a test asks for it
+pub struct TestClient {
+	pub id: CreatureId,
+	writer: OwnedWriteHalf,
+	inbox: mpsc::UnboundedReceiver<Received>,
+	reading_task: JoinHandle<()>
+}
+
+impl TestClient {
+	pub async fn connect(address: SocketAddr, name: &str) -> Self {
+		let stream = TcpStream::connect(address).await.unwrap();
+		let (reader, mut writer) = stream.into_split();
+		let mut reader = BufReader::new(reader);
+
+		writer.write_packet(&ProtocolVersion(3)).await.unwrap();
+		assert_eq!(reader.read_id().await.unwrap(), ConnectionAcceptance::ID);
+
+		//see [Server::assign_id]
+		assert_eq!(reader.read_id().await.unwrap(), CreatureUpdate::ID);
+		let id = reader.read_arbitrary::<CreatureId>().await.unwrap();
+		reader.read_exact(&mut [0_u8; 4456]).await.unwrap();
+
+		writer.write_packet(&character(name).to_update(id)).await.unwrap();
+
+		let (sender, inbox) = mpsc::unbounded_channel();
+		let reading_task = tokio::spawn(read_forever(reader, sender));
+
+		Self { id, writer, inbox, reading_task }
+	}
+
+	pub async fn send<P: FromClient>(&mut self, packet: &P)
+		where OwnedWriteHalf: WriteCwData<P>
+	{
+		self.writer.write_packet(packet).await.unwrap();
+	}
+
+	pub async fn chat(&mut self, text: &str) {
+		self.send(&ChatMessageFromClient { text: text.into() }).await;
+	}
+
+	///discards received packets until `filter` matches one.
+	///panics if the connection closes or nothing matches in time
+	pub async fn expect<T>(&mut self, mut filter: impl FnMut(Received) -> Option<T>) -> T {
+		timeout(PATIENCE, async {
+			loop {
+				let received = self.inbox.recv().await.expect("connection closed unexpectedly");
+				if let Some(matched) = filter(received) {
+					return matched;
+				}
+			}
+		}).await.expect("expected packet was never received")
+	}
+
+	pub async fn expect_chat(&mut self, predicate: impl Fn(&str) -> bool) -> String {
+		self.expect(|received| match received {
+			Received::ChatMessage(message) if predicate(&message.text) => Some(message.text),
+			_ => None
+		}).await
+	}
+
+	pub async fn expect_creature_update(&mut self, predicate: impl Fn(&CreatureUpdate) -> bool) -> CreatureUpdate {
+		self.expect(|received| match received {
+			Received::CreatureUpdate(packet) if predicate(&packet) => Some(*packet),
+			_ => None
+		}).await
+	}
+
+	pub async fn expect_disconnect(&mut self) {
+		timeout(PATIENCE, async {
+			while self.inbox.recv().await.is_some() {}
+		}).await.expect("client was never disconnected");
+	}
+
+	///asserts that nothing matching `filter` arrives within a short period of time
+	pub async fn expect_none(&mut self, mut filter: impl FnMut(&Received) -> bool) {
+		let result = timeout(Duration::from_millis(500), async {
+			while let Some(received) = self.inbox.recv().await {
+				assert!(!filter(&received), "received unexpected packet: {received:?}");
+			}
+		}).await;
+
+		assert!(result.is_err(), "connection closed unexpectedly");
+	}
+}
+
+impl Drop for TestClient {
+	fn drop(&mut self) {
+		self.reading_task.abort();
+	}
+}
+
+async fn read_forever(mut reader: BufReader<OwnedReadHalf>, sender: mpsc::UnboundedSender<Received>) {
+	loop {
+		let received = match reader.read_id().await {
+			Ok(CreatureUpdate       ::ID) => reader.read_packet().await.map(Box::new).map(Received::CreatureUpdate),
+			Ok(ServerTick           ::ID) => reader.read_packet::<ServerTick>().await.map(|_| Received::ServerTick),
+			Ok(AirshipTraffic       ::ID) => reader.read_packet().await.map(Received::AirshipTraffic),
+			Ok(WorldUpdate          ::ID) => reader.read_packet().await.map(Box::new).map(Received::WorldUpdate),
+			Ok(IngameDatetime       ::ID) => reader.read_packet().await.map(Received::IngameDatetime),
+			Ok(ChatMessageFromServer::ID) => reader.read_packet().await.map(Received::ChatMessage),
+			Ok(MapSeed              ::ID) => reader.read_packet().await.map(Received::MapSeed),
+			Ok(unexpected_id) => panic!("server sent unexpected packet id {unexpected_id:?}"),
+			Err(_) => break //disconnected
+		};
+
+		let Ok(received) = received
+			else { break };
+
+		if sender.send(received).is_err() {
+			break;
+		}
+	}
+}
+
+///a level 1 human warrior fresh out of character creation, guaranteed to pass anti-cheat
+pub fn character(name: &str) -> Creature {
+	let mut character = Creature {
+		position: Point3::new(0x8020_0000_0000, 0x8020_0000_0000, 0),
+		rotation: EulerAngles::default(),
+		velocity: Vector3::zeros(),
+		acceleration: Vector3::zeros(),
+		velocity_extra: Vector3::zeros(),
+		head_tilt: 0.0,
+		flags_physics: FlagSet::default(),
+		affiliation: Affiliation::Player,
+		race: Race::HumanMale,
+		animation: Animation::Idle,
+		animation_time: 0,
+		combo: 0,
+		combo_timeout: 0,
+		appearance: Appearance {
+			creature_size: Hitbox { width: 0.96000004, depth: 0.96000004, height: 2.16 },
+			head_model: 1246,
+			hair_model: 1252,
+			hand_model: 430,
+			foot_model: 432,
+			body_model: 1,
+			tail_model: -1,
+			shoulder2model: -1,
+			wing_model: -1,
+			head_size: 1.01,
+			body_size: 1.0,
+			hand_size: 1.0,
+			foot_size: 0.98,
+			shoulder2size: 1.0,
+			weapon_size: 0.95,
+			tail_size: 0.8,
+			shoulder1size: 1.0,
+			wing_size: 1.0,
+			body_offset: Point3::new(0.0, 0.0, -5.0),
+			head_offset: Point3::new(0.0, 0.5, 5.0),
+			hand_offset: Point3::new(6.0, 0.0, 0.0),
+			foot_offset: Point3::new(3.0, 1.0, -10.5),
+			tail_offset: Point3::new(0.0, -8.0, 2.0),
+			..Default::default()
+		},
+		flags: FlagSet::default(),
+		effect_time_dodge: 0,
+		effect_time_stun: 0,
+		effect_time_fear: 0,
+		effect_time_chill: 0,
+		effect_time_wind: 0,
+		show_patch_time: 0,
+		occupation: Occupation::Warrior,
+		specialization: Specialization::Default,
+		mana_charge: 0.0,
+		unknown24: [0.0; 3],
+		unknown25: [0.0; 3],
+		aim_displacement: Point3::origin(),
+		health: 0.0,
+		mana: 0.0,
+		blocking_gauge: 1.0,
+		multipliers: [100.0, 1.0, 1.0, 1.0, 1.0].into(), //health, attack speed, damage, armor, resi
+		unknown31: 0,
+		unknown32: 0,
+		level: 1,
+		experience: 0,
+		master: CreatureId(0),
+		unknown36: 0,
+		rarity: 0,
+		unknown38: 0,
+		home_zone: Point3::origin(),
+		home: Point3::origin(),
+		zone_to_reveal: Point3::origin(),
+		unknown42: 0,
+		consumable: Item::default(),
+		equipment: Default::default(),
+		name: name.into(),
+		skill_tree: Default::default(),
+		mana_cubes: 0
+	};
+
+	character.health = character.maximum_health();
+	character
+}
@@ -0,0 +1,62 @@
+use crate::tests::harness::{CONFIG, Received, TestServer};
+
+#[tokio::test]
+async fn full_server_queues_then_rejects() {
+	let test_server = TestServer::with_config(&format!("slots = 1\nqueue_size = 1\n{CONFIG}")).await;
+	let alice = test_server.connect("alice").await;
+
+	let mut bob = test_server.connect_without_joining("bob").await;
+	bob.expect_chat(|text| text == "the server is full, you are #1 in the queue").await;
+	test_server.expect_rejection().await;
+
+	drop(alice);
+	bob.expect(|received| matches!(received, Received::MapSeed(_)).then_some(())).await;
+	bob.expect_chat(|text| text == "[+] bob").await;
+}
+
+#[tokio::test]
+async fn queued_players_learn_their_new_position() {
+	let test_server = TestServer::with_config(&format!("slots = 1\nqueue_size = 2\n{CONFIG}")).await;
+	let alice = test_server.connect("alice").await;
+
+	let mut bob = test_server.connect_without_joining("bob").await;
+	bob.expect_chat(|text| text == "the server is full, you are #1 in the queue").await;
+	let mut carol = test_server.connect_without_joining("carol").await;
+	carol.expect_chat(|text| text == "the server is full, you are #2 in the queue").await;
+
+	drop(alice);
+	bob.expect_chat(|text| text == "[+] bob").await;
+	carol.expect_chat(|text| text == "the server is full, you are #1 in the queue").await;
+}
+
+#[tokio::test]
+async fn queue_can_be_disabled() {
+	let test_server = TestServer::with_config(&format!("slots = 1\nqueue_size = 0\n{CONFIG}")).await;
+	let _alice = test_server.connect("alice").await;
+
+	test_server.expect_rejection().await;
+}
+
+#[tokio::test]
+async fn roles_of_the_address_can_bypass_the_limit() {
+	let test_server = TestServer::with_config(&format!("slots = 1\nqueue_size = 0\n{CONFIG}\n[roles.local]\naddresses = [\"127.0.0.1\"]\npermissions = [\"queue.bypass\"]")).await;
+	let _alice = test_server.connect("alice").await;
+	let _bob = test_server.connect("bob").await;
+
+	assert_eq!(test_server.server.players.read().await.len(), 2);
+}
+
+#[tokio::test]
+async fn logging_in_while_queued_can_bypass_the_limit() {
+	let test_server = TestServer::with_config(&format!("slots = 1\n{CONFIG}")).await;
+	let _alice = test_server.connect("alice").await;
+
+	let mut bob = test_server.connect_without_joining("bob").await;
+	bob.expect_chat(|text| text == "the server is full, you are #1 in the queue").await;
+	bob.chat("/who").await; //anything else is still discarded
+	bob.chat("/login wrong").await;
+	bob.expect_chat(|text| text == "wrong password").await;
+	bob.chat("/login admin").await; //the admin role is permitted to do anything
+	bob.expect_chat(|text| text == "login successful").await;
+	bob.expect_chat(|text| text == "[+] bob").await;
+}
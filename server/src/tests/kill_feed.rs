@@ -0,0 +1,43 @@
+use protocol::packet::{CreatureUpdate, Hit};
+
+use crate::tests::harness::{Received, TestServer};
+
+#[tokio::test]
+async fn death_without_attacker() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.send(&CreatureUpdate {
+		id: alice.id,
+		health: Some(0.0),
+		..Default::default()
+	}).await;
+
+	alice.expect_chat(|text| text == "alice died").await;
+}
+
+#[tokio::test]
+async fn kill_is_attributed_to_last_attacker() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.send(&Hit {
+		attacker: alice.id,
+		target: bob.id,
+		damage: 10.0,
+		..Default::default()
+	}).await;
+	bob.expect(|received| match received {
+		Received::WorldUpdate(world_update) if !world_update.hits.is_empty() => Some(()),
+		_ => None
+	}).await;
+
+	bob.send(&CreatureUpdate {
+		id: bob.id,
+		health: Some(0.0),
+		..Default::default()
+	}).await;
+
+	alice.expect_chat(|text| text == "alice killed bob").await;
+}
@@ -0,0 +1,38 @@
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::tests::harness::TestServer;
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Capture {
+	fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+		self.0.lock().unwrap().extend_from_slice(buffer);
+		Ok(buffer.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+#[tokio::test]
+async fn passwords_dont_get_logged() {
+	let capture = Capture::default();
+	let writer = capture.clone();
+	//the test runtime is single threaded, so this also covers the server's tasks
+	let _guard = tracing::subscriber::set_default(tracing_subscriber::fmt().json().with_writer(move || writer.clone()).finish());
+
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	alice.chat("/login admin").await;
+	alice.expect_chat(|text| text == "login successful").await;
+	alice.chat("hello there").await;
+	alice.expect_chat(|text| text == "hello there").await;
+
+	let log = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+	assert!(log.contains("hello there"), "{log}");
+	assert!(log.contains("\"command\":\"login\""), "{log}");
+	assert!(!log.contains("login admin"), "{log}");
+}
@@ -0,0 +1,32 @@
+use crate::tests::harness::TestServer;
+
+#[tokio::test]
+async fn metrics_reflect_activity() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let _bob = test_server.connect("bob").await;
+
+	alice.chat("/help").await;
+	alice.expect_chat(|text| text.starts_with("help")).await;
+
+	let metrics = test_server.server.render_metrics().await;
+	assert!(metrics.contains("\nberld_players 2\n"), "{metrics}");
+	assert!(metrics.contains("\nberld_commands_total{command=\"help\"} 1\n"), "{metrics}");
+	assert!(metrics.contains("# TYPE berld_broadcast_duration_seconds histogram\n"), "{metrics}");
+}
+
+#[tokio::test]
+async fn metrics_are_per_server() {
+	let busy_server = TestServer::start().await;
+	let idle_server = TestServer::start().await;
+	let _alice = busy_server.connect("alice").await;
+
+	let busy = busy_server.server.render_metrics().await;
+	assert!(!busy.contains("\nberld_packets_sent_total{type=\"creature_update\"} 0\n"), "{busy}");
+	assert!(!busy.contains("\nberld_compressed_bytes_total 0\n"), "{busy}");
+
+	let idle = idle_server.server.render_metrics().await;
+	assert!(idle.contains("\nberld_packets_received_total{type=\"creature_update\"} 0\n"), "{idle}");
+	assert!(idle.contains("\nberld_packets_sent_total{type=\"creature_update\"} 0\n"), "{idle}");
+	assert!(idle.contains("\nberld_compressed_bytes_total 0\n"), "{idle}");
+}
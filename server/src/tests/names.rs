@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use protocol::packet::CreatureUpdate;
+
+use crate::tests::harness::{CONFIG, TestServer};
+
+#[tokio::test]
+async fn duplicate_names_get_suffixed_and_reserved_ones_refused() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	let mut impostor = test_server.connect_without_joining("Alice").await;
+	impostor.expect_chat(|text| text == "someone named Alice is online already, so you are Alice2 here").await;
+	alice.expect_chat(|text| text == "[+] Alice2").await;
+
+	let mut server = test_server.connect_without_joining("server").await;
+	server.expect_chat(|text| text == "the name server is reserved, please pick another one").await;
+	server.expect_disconnect().await;
+
+	//changing names later on is subject to the same rules
+	alice.send(&CreatureUpdate {
+		id: alice.id,
+		name: Some("admin".into()),
+		..Default::default()
+	}).await;
+	alice.expect_chat(|text| text == "the name admin is reserved, please pick another one").await;
+	alice.chat("/who").await;
+	alice.expect_chat(|text| text.contains(" alice,") || text.ends_with(" alice")).await;
+}
+
+#[tokio::test]
+async fn names_with_surrounding_spaces_are_refused() {
+	let test_server = TestServer::start().await;
+	let _alice = test_server.connect("alice").await;
+
+	for name in [" admin", "server ", "alice "] {
+		let mut impostor = test_server.connect_without_joining(name).await;
+		impostor.expect_chat(|text| text == "your name must not start or end with spaces").await;
+		impostor.expect_disconnect().await;
+	}
+}
+
+#[tokio::test]
+async fn duplicate_names_can_be_rejected() {
+	let test_server = TestServer::with_config(&format!("{CONFIG}\n[names]\nduplicates = \"reject\"")).await;
+	let _alice = test_server.connect("alice").await;
+
+	let mut impostor = test_server.connect_without_joining("alice").await;
+	impostor.expect_chat(|text| text == "someone named alice is online already, please pick another name").await;
+	impostor.expect_disconnect().await;
+}
+
+#[tokio::test]
+async fn names_are_checked_again_when_leaving_the_queue() {
+	let test_server = TestServer::with_config(&format!("slots = 1\nqueue_size = 2\n{CONFIG}")).await;
+	let alice = test_server.connect("alice").await;
+
+	let mut bob = test_server.connect_without_joining("bob").await;
+	bob.expect_chat(|text| text == "the server is full, you are #1 in the queue").await;
+	//queued players count as taken, too
+	let mut impostor = test_server.connect_without_joining("Bob").await;
+	impostor.expect_chat(|text| text == "someone named Bob is online already, so you are Bob2 here").await;
+
+	//nothing gets checked while queued
+	bob.send(&CreatureUpdate {
+		id: bob.id,
+		name: Some("bob2".into()),
+		..Default::default()
+	}).await;
+	sleep(Duration::from_millis(100)).await;
+
+	drop(alice);
+	bob.expect_chat(|text| text == "someone named bob2 is online already, so you are bob22 here").await;
+	bob.expect_chat(|text| text == "[+] bob22").await;
+}
@@ -0,0 +1,58 @@
+use crate::tests::harness::{CONFIG, TestServer};
+
+const MODERATOR: &str = "\n[roles.moderator]\npermissions = [\"command.kick\", \"command.who_ip\", \"notifications.admin\"]";
+
+#[tokio::test]
+async fn help_lists_permitted_commands_only() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/help").await;
+	let help = alice.expect_chat(|text| text.starts_with("help")).await;
+	let commands = help.split([' ', ',']).collect::<Vec<_>>();
+	assert!(commands.contains(&"who") && !commands.contains(&"kick"), "{help}");
+
+	alice.chat("/login admin").await;
+	alice.expect_chat(|text| text == "login successful").await;
+	alice.chat("/help").await;
+	let help = alice.expect_chat(|text| text.starts_with("help")).await;
+	assert!(help.contains("kick"), "{help}");
+}
+
+#[tokio::test]
+async fn roles_can_be_granted_and_revoked() {
+	let test_server = TestServer::with_config(&format!("{CONFIG}{MODERATOR}")).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+	let _carol = test_server.connect("carol").await;
+
+	bob.chat("/kick carol").await;
+	bob.expect_chat(|text| text == "no permission").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/role add moderator bo").await; //partial names could hit the wrong player
+	alice.expect_chat(|text| text == "target not found").await;
+	alice.chat("/role add moderator bob").await;
+	bob.expect_chat(|text| text == "bob is now moderator").await;
+
+	//moderators receive admin notifications, including their own promotion
+	bob.expect_chat(|text| text == "bob is now moderator").await;
+	bob.chat("/who_ip").await;
+	bob.expect_chat(|text| text.contains("127.0.0.1")).await;
+	bob.chat("/role add admin bob").await;
+	bob.expect_chat(|text| text == "no permission").await;
+
+	alice.chat("/role remove moderator bob").await;
+	bob.expect_chat(|text| text == "bob is no longer moderator").await;
+	bob.chat("/who_ip").await;
+	bob.expect_chat(|text| text == "no permission").await;
+}
+
+#[tokio::test]
+async fn roles_are_assigned_by_address_and_support_wildcards() {
+	let test_server = TestServer::with_config(&format!("{CONFIG}\n[roles.local]\naddresses = [\"127.0.0.1\"]\npermissions = [\"command.*\"]")).await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/role alice").await;
+	alice.expect_chat(|text| text == "alice has local").await;
+}
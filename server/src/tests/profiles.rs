@@ -0,0 +1,59 @@
+use std::fs;
+
+use crate::tests::harness::{CONFIG, TestServer, data_dir};
+
+#[tokio::test]
+async fn roles_and_team_survive_a_reconnect() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/role add immune bob").await;
+	bob.expect_chat(|text| text == "bob is now immune").await;
+	bob.chat("/team 5").await;
+	bob.expect_chat(|text| text == "current team: 5").await;
+	drop(bob);
+	alice.expect_chat(|text| text == "[-] bob").await;
+
+	let mut bob = test_server.connect("bob").await;
+	alice.chat("/role bob").await;
+	alice.expect_chat(|text| text == "bob has immune").await;
+	bob.chat("/team").await;
+	bob.expect_chat(|text| text.contains("current team: 5")).await;
+}
+
+#[tokio::test]
+async fn roles_only_come_back_for_addresses_they_were_granted_to() {
+	let data_dir = data_dir("profile-roles");
+	//as left behind by someone who joined under bob's name before, from an address bob's roles weren't granted to
+	fs::create_dir_all(data_dir.join("profiles")).unwrap();
+	fs::write(data_dir.join("profiles/bob.json"), r#"{"name": "bob", "addresses": ["127.0.0.1"], "roles": ["immune"], "trusted_addresses": ["10.0.0.1"]}"#).unwrap();
+
+	let test_server = TestServer::with_config(&format!("data_dir = '{}'\n{CONFIG}", data_dir.display())).await;
+	let mut alice = test_server.connect("alice").await;
+	let _bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/role bob").await;
+	alice.expect_chat(|text| text == "bob has no roles").await;
+}
+
+#[tokio::test]
+async fn profiles_keep_notes_and_related_names() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	drop(test_server.connect("carol").await);
+	alice.expect_chat(|text| text == "[-] carol").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/note carol griefed the spawn").await;
+	alice.expect_chat(|text| text == "noted on carol").await;
+	alice.chat("/note dave hello").await;
+	alice.expect_chat(|text| text == "no such profile").await;
+
+	alice.chat("/profile carol").await;
+	let profile = alice.expect_chat(|text| text.starts_with("carol: first seen")).await;
+	assert!(profile.contains("addresses: 127.0.0.1"), "{profile}");
+	assert!(profile.contains(&format!("note by alice (player #{}) ", alice.id.0)) && profile.contains(" ago: griefed the spawn"), "{profile}"); //a second might have passed since
+}
@@ -0,0 +1,42 @@
+use protocol::packet::CreatureUpdate;
+use protocol::packet::creature_update::Affiliation;
+
+use crate::tests::harness::TestServer;
+
+#[tokio::test]
+async fn existing_players_are_enemies() {
+	let test_server = TestServer::start().await;
+	let alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	let alice_as_seen_by_bob = bob.expect_creature_update(|packet| packet.id == alice.id && packet.name.is_some()).await;
+	assert_eq!(alice_as_seen_by_bob.affiliation, Some(Affiliation::Enemy));
+	assert_eq!(alice_as_seen_by_bob.rarity, Some(4));
+}
+
+#[tokio::test]
+async fn teammates_are_not_attackable() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	let affiliation_update = CreatureUpdate {
+		id: alice.id,
+		affiliation: Some(Affiliation::Player),
+		..Default::default()
+	};
+
+	alice.send(&affiliation_update).await;
+	let broadcasted = bob.expect_creature_update(|packet| packet.id == alice.id && packet.affiliation.is_some() && packet.name.is_none()).await;
+	assert_eq!(broadcasted.affiliation, Some(Affiliation::Enemy));
+
+	alice.chat("/team 7").await;
+	alice.expect_chat(|text| text == "current team: 7").await;
+	bob.chat("/team 7").await;
+	let attackability_update = bob.expect_creature_update(|packet| packet.id == alice.id && packet.affiliation.is_some() && packet.name.is_none()).await;
+	assert_eq!(attackability_update.affiliation, Some(Affiliation::Player));
+
+	alice.send(&affiliation_update).await;
+	let broadcasted = bob.expect_creature_update(|packet| packet.id == alice.id && packet.affiliation.is_some() && packet.name.is_none()).await;
+	assert_eq!(broadcasted.affiliation, Some(Affiliation::Player));
+}
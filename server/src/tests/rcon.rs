@@ -0,0 +1,145 @@
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::tests::harness::{connect_when_listening, expect_dropped, free_port, CONFIG, PATIENCE, TestServer};
+
+async fn start() -> (TestServer, u16) {
+	start_with("").await
+}
+
+///`settings` are top level config keys
+async fn start_with(settings: &str) -> (TestServer, u16) {
+	let port = free_port();
+	let test_server = TestServer::with_config(&format!("rcon_port = {port}\nrcon_password = \"secret\"\n{settings}\n{CONFIG}")).await;
+	(test_server, port)
+}
+
+async fn read_line(reader: &mut BufReader<TcpStream>) -> String {
+	let mut line = String::new();
+	timeout(PATIENCE, reader.read_line(&mut line)).await.unwrap().unwrap();
+	line.trim_end().to_owned()
+}
+
+#[tokio::test]
+async fn line_protocol() {
+	let (test_server, port) = start().await;
+
+	let mut intruder = BufReader::new(connect_when_listening(port).await);
+	intruder.write_all(b"guess\n").await.unwrap();
+	assert_eq!(read_line(&mut intruder).await, "wrong password");
+
+	let mut admin = BufReader::new(connect_when_listening(port).await);
+	admin.write_all(b"secret\n").await.unwrap();
+	assert_eq!(read_line(&mut admin).await, "authenticated");
+
+	let mut alice = test_server.connect("alice").await;
+	assert_eq!(read_line(&mut admin).await, "[announcement] [+] alice");
+
+	admin.write_all(b"who\n").await.unwrap();
+	assert!(read_line(&mut admin).await.contains("alice"));
+
+	admin.write_all(b"say hi\n").await.unwrap();
+	let mut lines = [read_line(&mut admin).await, read_line(&mut admin).await];
+	lines.sort(); //events are forwarded independently of responses
+	assert_eq!(lines, ["[announcement] hi", "ok"]);
+	alice.expect_chat(|text| text == "hi").await;
+
+	alice.chat("hello").await;
+	assert_eq!(read_line(&mut admin).await, "[chat] alice: hello");
+
+	admin.write_all(b"kick alice\naudit kick alice\n").await.unwrap();
+	let mut responses = vec![];
+	while responses.len() < 2 {
+		let line = read_line(&mut admin).await;
+		if !line.starts_with('[') {
+			responses.push(line);
+		}
+	}
+	assert!(responses[1].contains("rcon: kick alice -> ok"), "{responses:?}");
+}
+
+async fn send_packet(stream: &mut TcpStream, id: i32, kind: i32, body: &str) {
+	let mut bytes = Vec::new();
+	bytes.extend((body.len() as i32 + 10).to_le_bytes());
+	bytes.extend(id.to_le_bytes());
+	bytes.extend(kind.to_le_bytes());
+	bytes.extend(body.as_bytes());
+	bytes.extend([0, 0]);
+	stream.write_all(&bytes).await.unwrap();
+}
+
+async fn receive_packet(stream: &mut TcpStream) -> (i32, i32, String) {
+	timeout(PATIENCE, async {
+		let size = stream.read_i32_le().await.unwrap();
+		let id = stream.read_i32_le().await.unwrap();
+		let kind = stream.read_i32_le().await.unwrap();
+		let mut body = vec![0; size as usize - 8];
+		stream.read_exact(&mut body).await.unwrap();
+		body.truncate(body.len() - 2);
+		(id, kind, String::from_utf8(body).unwrap())
+	}).await.unwrap()
+}
+
+#[tokio::test]
+async fn source_protocol() {
+	let (test_server, port) = start().await;
+
+	let mut intruder = connect_when_listening(port).await;
+	send_packet(&mut intruder, 7, 3, "guess").await;
+	assert_eq!(receive_packet(&mut intruder).await, (7, 0, String::new()));
+	assert_eq!(receive_packet(&mut intruder).await, (-1, 2, String::new()));
+
+	let mut admin = connect_when_listening(port).await;
+	send_packet(&mut admin, 1, 3, "secret").await;
+	assert_eq!(receive_packet(&mut admin).await, (1, 0, String::new()));
+	assert_eq!(receive_packet(&mut admin).await, (1, 2, String::new()));
+
+	let _alice = test_server.connect("alice").await;
+	assert_eq!(receive_packet(&mut admin).await, (-1, 0, "[announcement] [+] alice".into()));
+
+	send_packet(&mut admin, 2, 2, "who").await;
+	let (id, kind, body) = receive_packet(&mut admin).await;
+	assert_eq!((id, kind), (2, 0));
+	assert!(body.contains("alice"), "{body}");
+
+	send_packet(&mut admin, 3, 2, "kick alice").await;
+	let (id, _kind, body) = receive_packet(&mut admin).await;
+	assert_eq!(id, -1);
+	assert!(body.starts_with("[announcement] kicked alice"), "{body}");
+	assert_eq!(receive_packet(&mut admin).await, (3, 0, String::new()));
+}
+
+#[tokio::test]
+async fn stalled_logins_get_dropped() {
+	let (_test_server, port) = start_with("handshake_timeout = 1").await;
+
+	expect_dropped(connect_when_listening(port).await).await;
+
+	let mut half_typed = connect_when_listening(port).await;
+	half_typed.write_all(b"sec").await.unwrap();
+	expect_dropped(half_typed).await;
+}
+
+#[tokio::test]
+async fn excess_connections_per_ip_get_dropped() {
+	let (_test_server, port) = start_with("max_connections_per_ip = 1").await;
+	let _idle = connect_when_listening(port).await;
+
+	expect_dropped(connect_when_listening(port).await).await;
+}
+
+#[tokio::test]
+async fn failed_logins_get_throttled_per_ip() {
+	let (_test_server, port) = start().await;
+
+	for _ in 0..3 {
+		let mut intruder = BufReader::new(connect_when_listening(port).await);
+		intruder.write_all(b"guess\n").await.unwrap();
+		assert_eq!(read_line(&mut intruder).await, "wrong password");
+	}
+
+	let mut admin = BufReader::new(connect_when_listening(port).await);
+	admin.write_all(b"secret\n").await.unwrap();
+	assert_eq!(read_line(&mut admin).await, "wrong password");
+}
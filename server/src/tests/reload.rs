@@ -0,0 +1,69 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crate::tests::harness::{CONFIG, TestServer, data_dir, parse_config};
+
+#[tokio::test]
+async fn reload_swaps_in_new_values() {
+	let toml = Arc::new(Mutex::new(CONFIG.to_owned()));
+	let source = Arc::clone(&toml);
+	let test_server = TestServer::with_loader(Box::new(move || parse_config(&source.lock().unwrap()))).await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login admin").await;
+	alice.expect_chat(|text| text == "login successful").await;
+
+	*toml.lock().unwrap() = r#"
+		motd = "fresh motd"
+		[roles.admin]
+		password = "changed"
+		permissions = ["*"]
+		[warps]
+		castle = [1, 2, 3]
+	"#.into();
+	alice.chat("/reload").await;
+	alice.expect_chat(|text| text == "config reloaded").await;
+
+	alice.chat("/warp").await;
+	alice.expect_chat(|text| text == "---\navailable locations:\ncastle\n---").await;
+
+	let mut bob = test_server.connect("bob").await;
+	bob.expect_chat(|text| text == "fresh motd\n2 player(s) online").await;
+	bob.chat("/login admin").await;
+	bob.expect_chat(|text| text == "wrong password").await;
+	bob.chat("/login changed").await;
+	bob.expect_chat(|text| text == "login successful").await;
+}
+
+#[tokio::test]
+async fn invalid_configs_are_not_applied() {
+	let toml = Arc::new(Mutex::new(CONFIG.to_owned()));
+	let source = Arc::clone(&toml);
+	let test_server = TestServer::with_loader(Box::new(move || parse_config(&source.lock().unwrap()))).await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login admin").await;
+	*toml.lock().unwrap() = "motd = \"never shown\"\nslots = 0".into();
+	alice.chat("/reload").await;
+	alice.expect_chat(|text| text == "config unchanged, reload failed: slots must be at least 1").await;
+
+	assert_eq!(*test_server.server.motd.read().await, "welcome to the test server");
+}
+
+#[tokio::test]
+async fn broken_models_are_caught_before_anything_is_applied() {
+	let model = data_dir("broken-model").join("broken.zox");
+	fs::write(&model, "not a model").unwrap();
+
+	let toml = Arc::new(Mutex::new(CONFIG.to_owned()));
+	let source = Arc::clone(&toml);
+	let test_server = TestServer::with_loader(Box::new(move || parse_config(&source.lock().unwrap()))).await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login admin").await;
+	*toml.lock().unwrap() = format!("motd = \"never shown\"\n[models]\n'{}' = [0, 0, 0]", model.display());
+	alice.chat("/reload").await;
+	alice.expect_chat(|text| text.starts_with("config unchanged, reload failed: model file '") && text.contains("broken.zox' can't be parsed: ")).await;
+
+	assert_eq!(*test_server.server.motd.read().await, "welcome to the test server");
+}
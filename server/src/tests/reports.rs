@@ -0,0 +1,37 @@
+use protocol::packet::CreatureUpdate;
+
+use crate::tests::harness::{CONFIG, TestServer, data_dir};
+
+#[tokio::test]
+async fn reports_reach_moderators_and_are_rate_limited() {
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir("reports").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut carol = test_server.connect("carol").await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	carol.chat("/login admin").await;
+	carol.expect_chat(|text| text == "login successful").await;
+	alice.chat("/login immune").await;
+	alice.expect_chat(|text| text == "login successful").await;
+	alice.send(&CreatureUpdate {
+		id: alice.id,
+		level: Some(9999),
+		..Default::default()
+	}).await;
+
+	bob.chat("/report bob test").await;
+	bob.expect_chat(|text| text == "you can't report yourself").await;
+	bob.chat("/report ali flying around").await;
+	bob.expect_chat(|text| text == "target not found").await;
+	bob.chat("/report alice flying around").await;
+	bob.expect_chat(|text| text == "thanks, the moderators got your report (#1)").await;
+	let report = carol.expect_chat(|text| text.starts_with("report #1 by bob against alice: flying around")).await;
+	assert!(report.contains("(immune)"), "{report}");
+
+	bob.chat("/report alice still flying").await;
+	bob.expect_chat(|text| text.starts_with("please wait")).await;
+
+	carol.chat("/reports").await;
+	carol.expect_chat(|text| text.contains("report #1 by bob against alice") && !text.contains("report #2")).await;
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use protocol::nalgebra::{Point2, Point3};
+use protocol::packet::AreaRequest;
+use protocol::packet::area_request::Zone;
+use protocol::packet::common::Item;
+use protocol::utils::constants::SIZE_BLOCK;
+
+use crate::server::shutdown::Outcome;
+use crate::tests::harness::{CONFIG, Received, TestServer, data_dir};
+
+#[tokio::test]
+async fn shutdown_command_disconnects_everyone() {
+	let data_dir = data_dir("shutdown");
+	let mut test_server = TestServer::with_config(&format!("data_dir = '{}'\n{CONFIG}", data_dir.display())).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/shutdown 1 maintenance").await;
+
+	bob.expect_chat(|text| text == "server shutting down in 1 second (maintenance)").await;
+	bob.expect_chat(|text| text == "server is shutting down now").await;
+	bob.expect_disconnect().await;
+	alice.expect_disconnect().await;
+
+	assert_eq!(test_server.stopped().await, Outcome::Exit);
+	assert!(test_server.server.players.read().await.is_empty());
+	assert!(data_dir.join("loot.bin").is_file());
+}
+
+#[tokio::test]
+async fn shutdown_can_be_cancelled() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/restart 18446744073709551615").await;
+	alice.expect_chat(|text| text == "delay too long, at most 1 day is allowed").await;
+	alice.chat("/restart 2").await;
+	alice.expect_chat(|text| text == "server restarting in 2 seconds").await;
+	alice.chat("/restart cancel").await;
+	alice.expect_chat(|text| text == "shutdown cancelled").await;
+
+	alice.expect_none(|received| matches!(received, Received::ChatMessage(message) if message.text.contains("restarting"))).await;
+	alice.chat("/restart cancel").await;
+	alice.expect_chat(|text| text == "no shutdown scheduled").await;
+}
+
+#[tokio::test]
+async fn loot_survives_restarts() {
+	let data_dir = data_dir("loot");
+	let config = format!("data_dir = '{}'\n{CONFIG}", data_dir.display());
+
+	let mut test_server = TestServer::with_config(&config).await;
+	test_server.server.add_drop(Item::default(), Point3::new(1, 2, 3), 0.0).await;
+	test_server.server.schedule_shutdown(Duration::ZERO, None, Outcome::Restart).await;
+	assert_eq!(test_server.stopped().await, Outcome::Restart);
+
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	alice.send(&AreaRequest::<Zone>(Point2::new(0, 0))).await;
+
+	let loot = alice.expect(|received| match received {
+		Received::WorldUpdate(world_update) if !world_update.p48.is_empty() => Some(world_update.loot),
+		_ => None
+	}).await;
+	assert_eq!(loot[&Point2::new(0, 0)][0].position, Point3::new(1, 2, 3 + SIZE_BLOCK / 10));
+}
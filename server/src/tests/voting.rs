@@ -0,0 +1,53 @@
+use crate::tests::harness::{CONFIG, TestServer, data_dir};
+
+#[tokio::test]
+async fn successful_vote_kicks_get_banned_for_a_while() {
+	let config = format!("data_dir = '{}'\nmax_connections_per_ip = 5\n{CONFIG}", data_dir("voting").display()); //carol reconnects while her old connection might still be closing
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+	let mut carol = test_server.connect("carol").await;
+	let mut dave = test_server.connect("dave").await;
+
+	alice.chat("/votekick carol griefing").await;
+	carol.expect_chat(|text| text.starts_with("alice wants to vote on kicking carol (griefing)")).await;
+	carol.chat("/vote no").await;
+	carol.expect_chat(|text| text == "you can't vote on your own kick").await;
+	bob.chat("/vote yes").await;
+	dave.chat("/vote yes").await;
+
+	alice.expect_chat(|text| text == "the vote on kicking carol (griefing) passed (3 yes, 0 no, 2 needed)").await;
+	carol.expect_disconnect().await;
+
+	let mut carol = test_server.connect_without_joining("carol").await;
+	carol.expect_chat(|text| text.starts_with("you are banned: vote-kick: griefing (")).await; //the time left might have ticked down to 9m 59s
+}
+
+#[tokio::test]
+async fn vote_kicks_need_enough_players_online() {
+	let test_server = TestServer::start().await;
+	let mut alice = test_server.connect("alice").await;
+	let _bob = test_server.connect("bob").await;
+
+	alice.chat("/votekick bob").await;
+	alice.expect_chat(|text| text == "not enough players online to vote-kick").await;
+	alice.chat("/vote yes").await;
+	alice.expect_chat(|text| text == "there is no vote in progress").await;
+}
+
+#[tokio::test]
+async fn polls_end_when_the_time_is_up() {
+	let config = format!("{CONFIG}\n[voting]\nduration = 1");
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+	let mut bob = test_server.connect("bob").await;
+
+	alice.chat("/poll pizza tonight?").await;
+	bob.expect_chat(|text| text.starts_with("alice asks: pizza tonight?")).await;
+	bob.chat("/poll or burgers?").await;
+	bob.expect_chat(|text| text == "another vote is in progress").await;
+
+	bob.expect_chat(|text| text == "the vote on 'pizza tonight?' passed (1 yes, 0 no, 1 needed)").await;
+	bob.chat("/vote no").await;
+	bob.expect_chat(|text| text == "there is no vote in progress").await;
+}
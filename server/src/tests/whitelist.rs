@@ -0,0 +1,30 @@
+use crate::tests::harness::{CONFIG, TestServer, data_dir};
+
+#[tokio::test]
+async fn only_listed_players_may_join_while_enabled() {
+	let config = format!("data_dir = '{}'\nwhitelist_message = \"private event\"\n{CONFIG}", data_dir("whitelist").display());
+	let test_server = TestServer::with_config(&config).await;
+	let mut alice = test_server.connect("alice").await;
+
+	alice.chat("/login admin").await;
+	alice.chat("/whitelist on").await;
+	alice.expect_chat(|text| text == "enabled the whitelist").await;
+	alice.chat("/whitelist on").await;
+	alice.expect_chat(|text| text == "nothing changed").await;
+
+	let mut bob = test_server.connect_without_joining("bob").await;
+	bob.expect_chat(|text| text == "private event").await;
+	bob.expect_disconnect().await;
+
+	alice.chat("/whitelist add bob").await;
+	alice.expect_chat(|text| text == "added bob to the whitelist").await;
+	test_server.connect("bob").await;
+	drop(test_server);
+
+	//both the entries and the whitelist being enabled persist
+	let test_server = TestServer::with_config(&config).await;
+	test_server.connect("bob").await;
+	let mut carol = test_server.connect_without_joining("carol").await;
+	carol.expect_chat(|text| text == "private event").await;
+	carol.expect_disconnect().await;
+}